@@ -39,3 +39,7 @@ demonstrate! {
         }
     }
 }
+
+fn main() {
+    println!("is_4() returns {}", is_4());
+}
@@ -0,0 +1,1435 @@
+//! Defines the various blocks used by the `demonstrate!` macro and their corresponding `Parse`
+//! implementations.
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::buffer::Cursor;
+use syn::parse::discouraged::Speculative;
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::spanned::Spanned;
+use syn::token::{Brace, Paren};
+use syn::{
+    braced, bracketed, parenthesized, parse_quote, Attribute, Expr, ExprLit, Ident, Lit, LitInt,
+    LitStr, Local, Meta, MetaNameValue, Pat, PatIdent, PatType, Stmt, Token, Type, UseTree,
+};
+
+/// Custom keywords used for the new blocks available in the `demonstrate!` macro
+mod keyword {
+    use syn::custom_keyword;
+
+    custom_keyword!(before);
+
+    custom_keyword!(after);
+
+    custom_keyword!(before_all);
+
+    custom_keyword!(after_all);
+
+    custom_keyword!(around);
+
+    custom_keyword!(fixture);
+
+    custom_keyword!(subject);
+
+    custom_keyword!(shared);
+
+    custom_keyword!(it_behaves_like);
+
+    // Are aliases for eachother:
+    custom_keyword!(describe);
+    custom_keyword!(context);
+
+    // Are aliases for eachother:
+    custom_keyword!(it);
+    custom_keyword!(test);
+
+    // Skipped variants of the block types above:
+    custom_keyword!(xdescribe);
+    custom_keyword!(xcontext);
+    custom_keyword!(xit);
+    custom_keyword!(xtest);
+
+    // Focused variants of the block types above:
+    custom_keyword!(fdescribe);
+    custom_keyword!(fcontext);
+    custom_keyword!(fit);
+    custom_keyword!(ftest);
+
+    custom_keyword!(skip);
+
+    custom_keyword!(timeout);
+
+    custom_keyword!(retry);
+
+    custom_keyword!(serial);
+
+    custom_keyword!(parallel);
+
+    custom_keyword!(max);
+
+    custom_keyword!(forall);
+}
+
+/// The tokens which can start a block, used to find the next block after one that fails to parse
+const BLOCK_STARTS: &[&str] = &[
+    "use",
+    "let",
+    "async",
+    "before",
+    "after",
+    "before_all",
+    "after_all",
+    "around",
+    "fixture",
+    "subject",
+    "shared",
+    "it_behaves_like",
+    "describe",
+    "context",
+    "it",
+    "test",
+    "xdescribe",
+    "xcontext",
+    "xit",
+    "xtest",
+    "fdescribe",
+    "fcontext",
+    "fit",
+    "ftest",
+    "skip",
+];
+
+/// The errors of sibling blocks, which are combined so that they're all reported at once
+#[derive(Default)]
+pub(crate) struct Errors(Option<Error>);
+
+impl Errors {
+    pub(crate) fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Parses a block, or records its error and skips past it so that its siblings can still be
+    /// parsed
+    fn parse<T: Parse>(&mut self, input: ParseStream) -> Option<T> {
+        let fork = input.fork();
+        match fork.parse::<T>() {
+            Ok(block) => {
+                input.advance_to(&fork);
+                Some(block)
+            }
+            Err(error) => {
+                self.push(error);
+                skip_block(input);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Skips the tokens of a block, which ends with either curly braces or a semicolon that are
+/// followed by the start of another block
+fn skip_block(input: ParseStream) {
+    let _ = input.step(|cursor| {
+        let mut rest = *cursor;
+        let mut is_terminated = false;
+        while let Some((tree, next)) = rest.token_tree() {
+            if is_terminated && starts_block(rest) {
+                break;
+            }
+            is_terminated = match tree {
+                TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+                TokenTree::Punct(punct) => punct.as_char() == ';',
+                _ => false,
+            };
+            rest = next;
+        }
+        Ok(((), rest))
+    });
+}
+
+fn starts_block(cursor: Cursor) -> bool {
+    if let Some((punct, _)) = cursor.punct() {
+        punct.as_char() == '#'
+    } else if let Some((ident, _)) = cursor.ident() {
+        BLOCK_STARTS.contains(&ident.to_string().as_str())
+    } else {
+        false
+    }
+}
+
+/// All the root blocks defined in the current `demonstrate!` instance
+pub(crate) struct Root {
+    /// The root `Describe` blocks
+    pub(crate) blocks: Vec<Describe>,
+    /// The shared groups available to every root `Describe` block
+    pub(crate) shared: Vec<Shared>,
+    /// Whether any block is focused, in which case only the tests within focused blocks run
+    pub(crate) is_focused: bool,
+}
+
+impl Parse for Root {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut module_prefix = String::new();
+        for attribute in input.call(Attribute::parse_inner)? {
+            module_prefix = parse_module_prefix(&attribute)?;
+        }
+
+        let mut blocks = Vec::new();
+        let mut shared = Vec::new();
+        let mut errors = Errors::default();
+        while !input.is_empty() {
+            if input.peek(keyword::shared) {
+                shared.extend(errors.parse::<Shared>(input));
+            } else {
+                blocks.extend(errors.parse::<Describe>(input));
+            }
+        }
+        errors.finish()?;
+
+        if !module_prefix.is_empty() {
+            for describe in &mut blocks {
+                describe.set_module_prefix(&module_prefix);
+            }
+            for shared in &mut shared {
+                shared.describe.set_module_prefix(&module_prefix);
+            }
+        }
+
+        Ok(Root {
+            blocks,
+            shared,
+            is_focused: false,
+        })
+    }
+}
+
+/// Parses the `#![module_prefix = "<prefix>"]` option, which prefixes the name of every module
+/// generated for a `Describe` block
+fn parse_module_prefix(attribute: &Attribute) -> Result<String> {
+    let error = || {
+        Error::new_spanned(
+            attribute,
+            "Expected `#![module_prefix = \"<prefix>\"]`, which is the only option",
+        )
+    };
+    let prefix = match attribute.parse_meta()? {
+        Meta::NameValue(MetaNameValue {
+            path,
+            lit: Lit::Str(prefix),
+            ..
+        }) if path.is_ident("module_prefix") => prefix,
+        _ => return Err(error()),
+    };
+
+    let value = prefix.value();
+    if value.starts_with(|c: char| c.is_ascii_digit())
+        || !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(Error::new(
+            prefix.span(),
+            "The module prefix may only contain ASCII letters, digits and underscores, and can't \
+             start with a digit",
+        ));
+    }
+
+    Ok(value)
+}
+
+/// The block types that can exist in a `Describe` block with corresponding `BlockProps`
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Block {
+    Describe(Describe),
+    Test(Test),
+    /// An `it_behaves_like` statement, which is replaced by the shared group it refers to before
+    /// generation
+    ItBehavesLike(ItBehavesLike),
+}
+
+impl Parse for Block {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Create forked stream for determining block type, passing the original input stream to
+        // the block's respective parse function
+        let fork = input.fork();
+
+        // These properties are parsed in the `Parse` implementation for `BlockProps`
+        let _attibutes = fork.call(Attribute::parse_outer)?;
+        if fork.parse::<Option<keyword::skip>>()?.is_some() {
+            let _reason = fork.parse::<LitStr>()?;
+        }
+        let _async_token = fork.parse::<Option<Token![async]>>()?;
+
+        let lookahead = fork.lookahead1();
+        if lookahead.peek(keyword::it)
+            || lookahead.peek(keyword::test)
+            || lookahead.peek(keyword::xit)
+            || lookahead.peek(keyword::xtest)
+            || lookahead.peek(keyword::fit)
+            || lookahead.peek(keyword::ftest)
+        {
+            Ok(Block::Test(input.parse::<Test>()?))
+        } else if lookahead.peek(keyword::describe)
+            || lookahead.peek(keyword::context)
+            || lookahead.peek(keyword::xdescribe)
+            || lookahead.peek(keyword::xcontext)
+            || lookahead.peek(keyword::fdescribe)
+            || lookahead.peek(keyword::fcontext)
+        {
+            Ok(Block::Describe(input.parse::<Describe>()?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// The `describe`/`context` block type
+#[derive(Clone)]
+pub(crate) struct Describe {
+    /// The properties that are either parsed or inherited by ancestoral Describe blocks
+    pub(crate) properties: DescribeProps,
+    /// The types or constants this block is instantiated for, if it is parameterized
+    pub(crate) instances: Option<Instances>,
+    /// The shared groups declared within this block instance
+    pub(crate) shared: Vec<Shared>,
+    /// The nested `Describe` blocks and contained `Test` blocks for this block instance
+    pub(crate) blocks: Vec<Block>,
+    /// The configured prefix of the modules generated for this block and its descendants
+    pub(crate) module_prefix: String,
+}
+
+impl Parse for Describe {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let block_props = input.parse::<BlockProps>()?;
+        let instances = if input.peek(Token![for]) {
+            Some(input.parse::<Instances>()?)
+        } else {
+            None
+        };
+
+        let mut describe = Describe::parse_content(block_props, input)?;
+        describe.instances = instances;
+        Ok(describe)
+    }
+}
+
+impl Describe {
+    /// Parses the curly braces following the `BlockProps` of a `Describe` block, which are also
+    /// used by shared groups
+    fn parse_content(block_props: BlockProps, input: ParseStream) -> Result<Self> {
+        let content;
+        braced!(content in input);
+
+        let mut uses = Vec::new();
+        let mut before = None;
+        let mut after = None;
+        let mut before_all = None;
+        let mut after_all = None;
+        let mut around = None;
+        let mut lets = Vec::<Let>::new();
+        let mut fixtures = Vec::<Fixture>::new();
+        let mut subject = None;
+        let mut shared = Vec::new();
+        let mut blocks = Vec::new();
+
+        let mut errors = Errors::default();
+        while !content.is_empty() {
+            if content.parse::<Option<Token![use]>>()?.is_some() {
+                uses.push(content.parse::<UseTree>()?);
+                content.parse::<Token![;]>()?;
+                continue;
+            }
+
+            let span = content.span();
+            let block = match errors.parse::<DescribeBlock>(&content) {
+                Some(block) => block,
+                None => continue,
+            };
+            match block {
+                DescribeBlock::Before(block) => {
+                    if before.is_none() {
+                        before = Some(block);
+                    } else {
+                        errors.push(Error::new(
+                            span,
+                            "Only one `before` statement per describe/context block",
+                        ));
+                    }
+                }
+                DescribeBlock::After(block) => {
+                    if after.is_none() {
+                        after = Some(block);
+                    } else {
+                        errors.push(Error::new(
+                            span,
+                            "Only one `after` statement per describe/context block",
+                        ));
+                    }
+                }
+                DescribeBlock::BeforeAll(block) => {
+                    if before_all.is_none() {
+                        before_all = Some(block);
+                    } else {
+                        errors.push(Error::new(
+                            span,
+                            "Only one `before_all` statement per describe/context block",
+                        ));
+                    }
+                }
+                DescribeBlock::AfterAll(block) => {
+                    if after_all.is_none() {
+                        after_all = Some(block);
+                    } else {
+                        errors.push(Error::new(
+                            span,
+                            "Only one `after_all` statement per describe/context block",
+                        ));
+                    }
+                }
+                DescribeBlock::Around(mut block) => {
+                    if around.is_none() {
+                        block.is_async = block_props.is_async;
+                        around = Some(block);
+                    } else {
+                        errors.push(Error::new(
+                            span,
+                            "Only one `around` statement per describe/context block",
+                        ));
+                    }
+                }
+                DescribeBlock::Let(block) => {
+                    if lets.iter().any(|other| other.name == block.name) {
+                        errors.push(Error::new(
+                            block.name.span(),
+                            format!(
+                                "`let {}` is already declared in this describe/context block",
+                                block.name
+                            ),
+                        ));
+                    } else {
+                        lets.push(block);
+                    }
+                }
+                DescribeBlock::Fixture(block) => {
+                    if fixtures.iter().any(|other| other.name == block.name) {
+                        errors.push(Error::new(
+                            block.name.span(),
+                            format!(
+                                "`fixture {}` is already declared in this describe/context block",
+                                block.name
+                            ),
+                        ));
+                    } else {
+                        fixtures.push(block);
+                    }
+                }
+                DescribeBlock::Subject(block) => {
+                    if subject.is_none() {
+                        subject = Some(block);
+                    } else {
+                        errors.push(Error::new(
+                            span,
+                            "Only one `subject` statement per describe/context block",
+                        ));
+                    }
+                }
+                DescribeBlock::Shared(block) => shared.push(block),
+                DescribeBlock::ItBehavesLike(block) => blocks.push(Block::ItBehavesLike(block)),
+                DescribeBlock::Regular(block) => blocks.push(block),
+            }
+        }
+        errors.finish()?;
+
+        let once_hooks = if before_all.is_some() || after_all.is_some() {
+            vec![OnceHooks {
+                index: 0,
+                is_async: block_props.is_async,
+                before_all,
+                after_all,
+            }]
+        } else {
+            Vec::new()
+        };
+
+        Ok(Describe {
+            properties: DescribeProps {
+                block_props,
+                uses,
+                before,
+                after,
+                once_hooks,
+                lets,
+                subject,
+                around: around.into_iter().collect(),
+                fixtures,
+            },
+            instances: None,
+            shared,
+            blocks,
+            module_prefix: String::new(),
+        })
+    }
+
+    /// Sets the prefix of the modules generated for this block and its descendants, including
+    /// those of the shared groups declared within it
+    fn set_module_prefix(&mut self, prefix: &str) {
+        self.module_prefix = prefix.to_string();
+        for shared in &mut self.shared {
+            shared.describe.set_module_prefix(prefix);
+        }
+        for block in &mut self.blocks {
+            if let Block::Describe(describe) = block {
+                describe.set_module_prefix(prefix);
+            }
+        }
+    }
+}
+
+/// A `for T in [<types>]` or `for const N: <type> in [<values>]` list, where each type or value
+/// generates a separate module in which `T` or `N` is declared
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Instances {
+    /// Each type is declared as a type alias
+    Types { name: Ident, types: Vec<Type> },
+    /// Each value is declared as a constant
+    Consts {
+        name: Ident,
+        ty: Type,
+        values: Vec<Expr>,
+    },
+}
+
+impl Parse for Instances {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![for]>()?;
+        let is_const = input.parse::<Option<Token![const]>>()?.is_some();
+        let name = input.parse::<Ident>()?;
+        let ty = if is_const {
+            input.parse::<Token![:]>()?;
+            Some(input.parse::<Type>()?)
+        } else {
+            None
+        };
+        input.parse::<Token![in]>()?;
+
+        let content;
+        bracketed!(content in input);
+        let instances = if let Some(ty) = ty {
+            let values = content
+                .parse_terminated::<Expr, Token![,]>(Expr::parse)?
+                .into_iter()
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                return Err(content.error("Expected at least one value"));
+            }
+            Instances::Consts { name, ty, values }
+        } else {
+            let types = content
+                .parse_terminated::<Type, Token![,]>(Type::parse)?
+                .into_iter()
+                .collect::<Vec<_>>();
+            if types.is_empty() {
+                return Err(content.error("Expected at least one type"));
+            }
+            Instances::Types { name, types }
+        };
+
+        Ok(instances)
+    }
+}
+
+/// Properties for `Describe` blocks that will be inherited and passed down to nested blocks
+#[derive(Clone)]
+pub(crate) struct DescribeProps {
+    /// The properties that will be passed to all descending tests
+    pub(crate) block_props: BlockProps,
+    /// The paths declared with `use` tokens within this block instance
+    pub(crate) uses: Vec<UseTree>,
+    /// The `before` block for this block instance
+    pub(crate) before: Option<BasicBlock>,
+    /// The `after` block for this block instance
+    pub(crate) after: Option<After>,
+    /// The `before_all`/`after_all` blocks for this block instance and its ancestors, outermost
+    /// first
+    pub(crate) once_hooks: Vec<OnceHooks>,
+    /// The `let` declarations for this block instance and the ones it didn't override from its
+    /// ancestors
+    pub(crate) lets: Vec<Let>,
+    /// The `subject` block for this block instance, or the one inherited from its closest ancestor
+    /// that declares one
+    pub(crate) subject: Option<BasicBlock>,
+    /// The `around` blocks for this block instance and its ancestors, outermost first
+    pub(crate) around: Vec<Around>,
+    /// The fixtures declared for this block instance and the ones it didn't override from its
+    /// ancestors
+    pub(crate) fixtures: Vec<Fixture>,
+}
+
+/// The `before_all` and `after_all` blocks of a `Describe` block, which run once for all of its
+/// descendant tests
+#[derive(Clone)]
+pub(crate) struct OnceHooks {
+    /// The depth of these hooks among the hooks of ancestoral `Describe` blocks, which keeps the
+    /// names of their generated items unique
+    pub(crate) index: usize,
+    /// Whether the `before_all` block is allowed to `.await`
+    pub(crate) is_async: bool,
+    /// The `before_all` block
+    pub(crate) before_all: Option<BeforeAll>,
+    /// The `after_all` block
+    pub(crate) after_all: Option<BasicBlock>,
+}
+
+/// All the blocks permitted within a `Describe` block
+#[allow(clippy::large_enum_variant)]
+enum DescribeBlock {
+    /// A nested `Describe` or `Test` block
+    Regular(Block),
+    /// A `before {}` block
+    Before(BasicBlock),
+    /// An `after {}` or `after |outcome| {}` block
+    After(After),
+    /// A `before_all {}` block
+    BeforeAll(BeforeAll),
+    /// An `after_all {}` block
+    AfterAll(BasicBlock),
+    /// An `around |run| {}` block
+    Around(Around),
+    /// A `let name = value;` declaration
+    Let(Let),
+    /// A `fixture name: Type = value;` declaration
+    Fixture(Fixture),
+    /// A `subject {}` block
+    Subject(BasicBlock),
+    /// A `shared "name" (params) {}` block
+    Shared(Shared),
+    /// An `it_behaves_like "name"(args);` statement
+    ItBehavesLike(ItBehavesLike),
+}
+
+impl Parse for DescribeBlock {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.parse::<Option<keyword::before>>()?.is_some() {
+            Ok(DescribeBlock::Before(input.parse::<BasicBlock>()?))
+        } else if input.parse::<Option<keyword::after>>()?.is_some() {
+            Ok(DescribeBlock::After(input.parse::<After>()?))
+        } else if input.parse::<Option<keyword::before_all>>()?.is_some() {
+            Ok(DescribeBlock::BeforeAll(input.parse::<BeforeAll>()?))
+        } else if input.parse::<Option<keyword::after_all>>()?.is_some() {
+            Ok(DescribeBlock::AfterAll(input.parse::<BasicBlock>()?))
+        } else if input.parse::<Option<keyword::around>>()?.is_some() {
+            Ok(DescribeBlock::Around(input.parse::<Around>()?))
+        } else if input.peek(Token![let]) {
+            Ok(DescribeBlock::Let(input.parse::<Let>()?))
+        } else if input.peek(keyword::fixture) {
+            Ok(DescribeBlock::Fixture(input.parse::<Fixture>()?))
+        } else if input.parse::<Option<keyword::subject>>()?.is_some() {
+            Ok(DescribeBlock::Subject(input.parse::<BasicBlock>()?))
+        } else if input.peek(keyword::shared) {
+            Ok(DescribeBlock::Shared(input.parse::<Shared>()?))
+        } else if input.peek(keyword::it_behaves_like) {
+            Ok(DescribeBlock::ItBehavesLike(
+                input.parse::<ItBehavesLike>()?,
+            ))
+        } else {
+            Ok(DescribeBlock::Regular(input.parse::<Block>()?))
+        }
+    }
+}
+
+/// A `shared "name" (params) {}` block, containing tests and nested `Describe` blocks that are
+/// included wherever an `it_behaves_like "name"(args);` statement refers to it
+#[derive(Clone)]
+pub(crate) struct Shared {
+    /// The name that `it_behaves_like` statements refer to
+    pub(crate) name: LitStr,
+    /// The parameters of the group, which are declared as `let` bindings when included
+    pub(crate) params: Vec<Param>,
+    /// The contents of the group, as a `Describe` block of the same name
+    pub(crate) describe: Describe,
+}
+
+impl Parse for Shared {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let keyword = input.parse::<keyword::shared>()?;
+        let name = parse_name(input)?;
+
+        let params = if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            content
+                .parse_terminated::<Param, Token![,]>(Param::parse)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let block_props = BlockProps {
+            attributes: Vec::new(),
+            is_async: false,
+            keyword: Ident::new("shared", keyword.span),
+            focus: None,
+            description: name.value(),
+            name: name.clone(),
+            path: Vec::new(),
+            timeout: None,
+            retries: None,
+            concurrency: None,
+            return_type: None,
+        };
+
+        Ok(Shared {
+            name,
+            params,
+            describe: Describe::parse_content(block_props, input)?,
+        })
+    }
+}
+
+/// A parameter of a shared group or a test requesting a fixture, e.g. `name` or `name: Type`
+#[derive(Clone)]
+pub(crate) struct Param {
+    /// The name of the parameter
+    pub(crate) name: Ident,
+    /// The type of the parameter, if specified
+    pub(crate) ty: Option<Type>,
+}
+
+impl Parse for Param {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Ident>()?;
+        let ty = if input.parse::<Option<Token![:]>>()?.is_some() {
+            Some(input.parse::<Type>()?)
+        } else {
+            None
+        };
+
+        Ok(Param { name, ty })
+    }
+}
+
+/// An `it_behaves_like "name"(args);` statement, including the shared group of the same name
+#[derive(Clone)]
+pub(crate) struct ItBehavesLike {
+    /// The name of the shared group
+    pub(crate) name: LitStr,
+    /// The arguments passed to the parameters of the shared group
+    pub(crate) args: Vec<Expr>,
+}
+
+impl Parse for ItBehavesLike {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<keyword::it_behaves_like>()?;
+        let name = parse_name(input)?;
+
+        let args = if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            content
+                .parse_terminated::<Expr, Token![,]>(Expr::parse)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        input.parse::<Token![;]>()?;
+
+        Ok(ItBehavesLike { name, args })
+    }
+}
+
+/// An `it`/`test` block
+#[derive(Clone)]
+pub(crate) struct Test {
+    /// The properties defined for this test, or inherited from ancestoral `Describe` blocks
+    pub(crate) properties: BlockProps,
+    /// The case table for this test, if it is parameterized
+    pub(crate) cases: Option<Cases>,
+    /// The generated inputs of this test, if it is a property test
+    pub(crate) forall: Option<Forall>,
+    /// Whether this test was declared without contents
+    pub(crate) is_pending: bool,
+    /// The `before_all`/`after_all` blocks inherited from ancestoral `Describe` blocks
+    pub(crate) once_hooks: Vec<OnceHooks>,
+    /// The `let` declarations inherited from ancestoral `Describe` blocks
+    pub(crate) lets: Vec<Let>,
+    /// The `subject` block inherited from the closest ancestoral `Describe` block declaring one
+    pub(crate) subject: Option<BasicBlock>,
+    /// The `around` blocks inherited from ancestoral `Describe` blocks, outermost first
+    pub(crate) around: Vec<Around>,
+    /// The fixtures this test requests as parameters
+    pub(crate) params: Vec<Param>,
+    /// The fixtures inherited from ancestoral `Describe` blocks
+    pub(crate) fixtures: Vec<Fixture>,
+    /// The `before` code sequences inherited from ancestoral `Describe` blocks
+    pub(crate) before: Vec<Stmt>,
+    /// The unique contents of this test
+    pub(crate) content: BasicBlock,
+    /// The `after` code sequences inherited from ancestoral `Describe` blocks
+    pub(crate) after: Option<After>,
+}
+
+impl Parse for Test {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut properties = input.parse::<BlockProps>()?;
+        let cases = if input.peek(Token![for]) {
+            Some(input.parse::<Cases>()?)
+        } else {
+            None
+        };
+        let forall = if input.peek(keyword::forall) {
+            if cases.is_some() {
+                return Err(input.error("A test can't have both cases and generated inputs"));
+            }
+            Some(input.parse::<Forall>()?)
+        } else {
+            None
+        };
+
+        // The fixtures that the test requests, e.g. `|db: &Db|`
+        let params = if input.parse::<Option<Token![|]>>()?.is_some() {
+            let mut params = Vec::new();
+            while !input.peek(Token![|]) {
+                params.push(input.parse::<Param>()?);
+                if input.parse::<Option<Token![,]>>()?.is_none() {
+                    break;
+                }
+            }
+            input.parse::<Token![|]>()?;
+
+            // As with closures, the return type can follow the parameters
+            if input.peek(Token![->]) {
+                if properties.return_type.is_some() {
+                    return Err(input.error("The return type is already declared for this test"));
+                }
+                input.parse::<Token![->]>()?;
+                properties.return_type = Some(input.parse::<Type>()?);
+            }
+            params
+        } else {
+            Vec::new()
+        };
+
+        // A test without contents is pending, and ignored until its contents are written
+        let semicolon = input.parse::<Option<Token![;]>>()?;
+        let is_pending = semicolon.is_some();
+        let content = if let Some(semicolon) = semicolon {
+            properties.ignore(Some("pending"));
+            BasicBlock(Vec::new(), semicolon.span)
+        } else if input.peek(Token![=>]) {
+            // A one-liner expects the subject to equal the value, which it's described as
+            input.parse::<Token![=>]>()?;
+            let expected = input.parse::<Expr>()?;
+            input.parse::<Option<Token![;]>>()?;
+            if properties.description.is_empty() {
+                properties.description = describe_tokens(quote!(is_expected.to(eq(#expected))));
+            }
+            let span = expected.span();
+            let content = parse_quote!(is_expected.to(::demonstrate::matchers::eq(#expected)););
+            BasicBlock(vec![content], span)
+        } else {
+            input.parse::<BasicBlock>()?
+        };
+
+        // A test without a name is described by its contents, e.g. "is expected to eq 5"
+        if properties.description.is_empty() {
+            let BasicBlock(stmts, _) = &content;
+            properties.description = describe_tokens(quote!(#(#stmts)*));
+        }
+
+        Ok(Test {
+            properties,
+            cases,
+            forall,
+            is_pending,
+            once_hooks: Vec::new(),
+            lets: Vec::new(),
+            subject: None,
+            around: Vec::new(),
+            params,
+            fixtures: Vec::new(),
+            before: Vec::new(),
+            content,
+            after: None,
+        })
+    }
+}
+
+/// Describes a test by its contents, reading identifiers as words (e.g. `is_expected.to(eq(5))`
+/// as "is expected to eq 5")
+fn describe_tokens(tokens: TokenStream) -> String {
+    fn push(description: &mut String, word: &str, is_joined: bool) {
+        if !is_joined && !description.is_empty() {
+            description.push(' ');
+        }
+        description.push_str(word);
+    }
+
+    let mut description = String::new();
+    let mut is_joined = false;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => push(
+                &mut description,
+                &ident.to_string().replace('_', " "),
+                is_joined,
+            ),
+            TokenTree::Literal(literal) => push(&mut description, &literal.to_string(), is_joined),
+            TokenTree::Group(group) => {
+                // Brackets and the delimiters of macro invocations are kept, e.g. `vec![1, 2]`
+                let inner = describe_tokens(group.stream());
+                let inner = match group.delimiter() {
+                    Delimiter::Bracket => format!("[{}]", inner),
+                    Delimiter::Parenthesis if description.ends_with('!') => format!("({})", inner),
+                    Delimiter::Brace if description.ends_with('!') => format!("{{{}}}", inner),
+                    _ => inner,
+                };
+                push(&mut description, &inner, is_joined)
+            }
+            TokenTree::Punct(punct) => match punct.as_char() {
+                // Method calls, statements and references read as spaces
+                '.' | ';' | '&' => {}
+                ',' => description.push(','),
+                '!' => {
+                    description.push('!');
+                    is_joined = true;
+                    continue;
+                }
+                c => {
+                    push(&mut description, &c.to_string(), is_joined);
+                    is_joined = true;
+                    continue;
+                }
+            },
+        }
+        is_joined = false;
+    }
+
+    description
+}
+
+/// A `for <pattern> in [<values>]` case table, where each value generates a separate test with
+/// the value bound to the pattern
+#[derive(Clone)]
+pub(crate) struct Cases {
+    /// The pattern that each value is bound to
+    pub(crate) pattern: Pat,
+    /// The values of each case
+    pub(crate) values: Vec<Expr>,
+}
+
+impl Parse for Cases {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pattern = input.parse::<Pat>()?;
+        input.parse::<Token![in]>()?;
+
+        let content;
+        bracketed!(content in input);
+        let values = content
+            .parse_terminated::<Expr, Token![,]>(Expr::parse)?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return Err(content.error("Expected at least one case"));
+        }
+
+        Ok(Cases { pattern, values })
+    }
+}
+
+/// A `forall (name: Type, name in strategy)` list of inputs, which are generated for each run of
+/// a property test
+#[derive(Clone)]
+pub(crate) struct Forall {
+    /// The name that each input is bound to
+    pub(crate) names: Vec<Ident>,
+    /// The strategy generating each input, which is either a range or the `Arbitrary`
+    /// implementation of the input's type
+    pub(crate) strategies: Vec<Expr>,
+}
+
+/// The most inputs a property test can have
+const MAX_INPUTS: usize = 8;
+
+impl Parse for Forall {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<keyword::forall>()?;
+
+        let content;
+        parenthesized!(content in input);
+        let mut names = Vec::new();
+        let mut strategies = Vec::new();
+        while !content.is_empty() {
+            names.push(content.parse::<Ident>()?);
+            strategies.push(if content.parse::<Option<Token![:]>>()?.is_some() {
+                let ty = content.parse::<Type>()?;
+                parse_quote!(::demonstrate::__private::any::<#ty>())
+            } else {
+                content.parse::<Token![in]>()?;
+                content.parse::<Expr>()?
+            });
+
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+
+        if names.is_empty() {
+            return Err(content.error("Expected at least one input"));
+        }
+        if names.len() > MAX_INPUTS {
+            return Err(Error::new(
+                names[MAX_INPUTS].span(),
+                format!("A property test can have at most {} inputs", MAX_INPUTS),
+            ));
+        }
+
+        Ok(Forall { names, strategies })
+    }
+}
+
+/// Simply lines of source code that were originally within curly braces, along with the span of
+/// those curly braces
+#[derive(Clone)]
+pub(crate) struct BasicBlock(pub(crate) Vec<Stmt>, pub(crate) Span);
+
+impl Parse for BasicBlock {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let braces = braced!(content in input);
+
+        Ok(BasicBlock(
+            content.call(syn::Block::parse_within)?,
+            braces.span,
+        ))
+    }
+}
+
+/// A `fixture name: Type = value;` declaration within a `Describe` block, which is only built for
+/// the descendant tests that request it as a parameter and can be overridden by nested `Describe`
+/// blocks
+#[derive(Clone)]
+pub(crate) struct Fixture {
+    /// The name that tests request the fixture by
+    pub(crate) name: Ident,
+    /// The type of the fixture
+    pub(crate) ty: Type,
+    /// The value the fixture is built from
+    pub(crate) value: Expr,
+}
+
+impl Parse for Fixture {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<keyword::fixture>()?;
+        let name = input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse::<Type>()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<Expr>()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(Fixture { name, ty, value })
+    }
+}
+
+/// A `let name = value;` declaration within a `Describe` block, which is only evaluated by the
+/// descendant tests that use it and can be overridden by nested `Describe` blocks
+#[derive(Clone)]
+pub(crate) struct Let {
+    /// The name of the binding
+    pub(crate) name: Ident,
+    /// The `let` statement itself
+    pub(crate) local: Local,
+}
+
+impl Parse for Let {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let local = match input.parse::<Stmt>()? {
+            Stmt::Local(local) => local,
+            stmt => return Err(Error::new_spanned(stmt, "Expected a `let` declaration")),
+        };
+
+        let pat = match &local.pat {
+            Pat::Type(PatType { pat, .. }) => &**pat,
+            pat => pat,
+        };
+        let name = match pat {
+            Pat::Ident(PatIdent {
+                by_ref: None,
+                subpat: None,
+                ident,
+                ..
+            }) => ident.clone(),
+            pat => {
+                return Err(Error::new_spanned(
+                    pat,
+                    "`let` declarations must bind a single identifier",
+                ))
+            }
+        };
+
+        if local.init.is_none() {
+            return Err(Error::new_spanned(
+                &local,
+                format!("`let {}` needs a value", name),
+            ));
+        }
+
+        Ok(Let { name, local })
+    }
+}
+
+/// An `after {}` block, which may bind the outcome of the test with `after |outcome| {}`
+#[derive(Clone)]
+pub(crate) struct After {
+    /// Whether the outcome of the test is bound within this block
+    pub(crate) uses_outcome: bool,
+    /// The lines of source code within the block, starting with the binding of the outcome
+    pub(crate) content: BasicBlock,
+}
+
+impl Parse for After {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let outcome = if input.parse::<Option<Token![|]>>()?.is_some() {
+            let pattern = input.parse::<Pat>()?;
+            input.parse::<Token![|]>()?;
+            Some(pattern)
+        } else {
+            None
+        };
+        let BasicBlock(mut stmts, span) = input.parse::<BasicBlock>()?;
+
+        // Bind the outcome as a statement so that `after` blocks can be joined while inherited
+        if let Some(pattern) = &outcome {
+            stmts.insert(
+                0,
+                syn::parse_quote!(let #pattern: &::demonstrate::Outcome = &__outcome;),
+            );
+        }
+
+        Ok(After {
+            uses_outcome: outcome.is_some(),
+            content: BasicBlock(stmts, span),
+        })
+    }
+}
+
+/// An `around |run| {}` block, which wraps each descendant test and runs it by calling `run()`
+#[derive(Clone)]
+pub(crate) struct Around {
+    /// The name of the closure that runs the test
+    pub(crate) run: Ident,
+    /// Whether `run()` returns a future to `.await`
+    pub(crate) is_async: bool,
+    /// The lines of source code within the block
+    pub(crate) content: BasicBlock,
+}
+
+impl Parse for Around {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![|]>()?;
+        let run = input.parse::<Ident>()?;
+        input.parse::<Token![|]>()?;
+
+        Ok(Around {
+            run,
+            is_async: false,
+            content: input.parse::<BasicBlock>()?,
+        })
+    }
+}
+
+/// A `before_all {}` block, whose `let` bindings are shared with all descendant tests
+#[derive(Clone)]
+pub(crate) struct BeforeAll {
+    /// The lines of source code within the block
+    pub(crate) stmts: Vec<Stmt>,
+    /// The names and types of the `let` bindings within the block
+    pub(crate) bindings: Vec<(Ident, Type)>,
+}
+
+impl Parse for BeforeAll {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let BasicBlock(stmts, _) = input.parse::<BasicBlock>()?;
+
+        // The bindings are stored in a `static`, so each of them needs a name and a type
+        let mut bindings = Vec::new();
+        for stmt in &stmts {
+            if let Stmt::Local(local) = stmt {
+                match &local.pat {
+                    Pat::Type(PatType { pat, ty, .. }) => match &**pat {
+                        Pat::Ident(PatIdent {
+                            by_ref: None,
+                            subpat: None,
+                            ident,
+                            ..
+                        }) => bindings.push((ident.clone(), (**ty).clone())),
+                        _ => {
+                            return Err(Error::new_spanned(
+                                pat,
+                                "`before_all` bindings must be a single identifier",
+                            ))
+                        }
+                    },
+                    pat => return Err(Error::new_spanned(
+                        pat,
+                        "`before_all` bindings must have a type, e.g. `let name: Type = value;`",
+                    )),
+                }
+            }
+        }
+
+        Ok(BeforeAll { stmts, bindings })
+    }
+}
+
+/// Properties that can apply to `Describe` and `Test` blocks
+#[derive(Clone)]
+pub(crate) struct BlockProps {
+    /// Outer attributes
+    pub(crate) attributes: Vec<Attribute>,
+    /// Whether this block or an ancestor was declared as `async`
+    pub(crate) is_async: bool,
+    /// The keyword this block was declared with, which the generated items are spanned to
+    pub(crate) keyword: Ident,
+    /// The `fdescribe`/`fcontext`/`fit`/`ftest` keyword, if this block was declared as focused
+    pub(crate) focus: Option<Ident>,
+    /// The name literal of this block, which errors about the name point at
+    pub(crate) name: LitStr,
+    /// The human-readable description of this block, which its identifier is generated from
+    pub(crate) description: String,
+    /// The descriptions of the ancestral blocks, outermost first
+    pub(crate) path: Vec<String>,
+    /// The time limit that was either defined for this block or an ancestor (if one was not
+    /// specified), as a `Duration` expression
+    pub(crate) timeout: Option<Expr>,
+    /// How many times failed tests are retried, which was either defined for this block or an
+    /// ancestor (if it was not specified)
+    pub(crate) retries: Option<u32>,
+    /// The limit on how many tests may run at once, which was either defined for this block or an
+    /// ancestor (if one was not specified)
+    pub(crate) concurrency: Option<Concurrency>,
+    /// The return type that was either defined for this block or an ancestor (if one was not
+    /// specified)
+    pub(crate) return_type: Option<Type>,
+}
+
+impl Parse for BlockProps {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let skip_reason = if input.parse::<Option<keyword::skip>>()?.is_some() {
+            Some(input.parse::<LitStr>()?)
+        } else {
+            None
+        };
+        let is_async = input.parse::<Option<Token![async]>>()?.is_some();
+        // The block type keyword is determined in the `Parse` implementation for `Block`, only
+        // the skipped and focused variants are relevant here
+        let block_type = input.parse::<Ident>()?;
+        let is_skipped = matches!(
+            block_type.to_string().as_str(),
+            "xdescribe" | "xcontext" | "xit" | "xtest"
+        );
+        let focus = if matches!(
+            block_type.to_string().as_str(),
+            "fdescribe" | "fcontext" | "fit" | "ftest"
+        ) {
+            Some(block_type.clone())
+        } else {
+            None
+        };
+        // Tests can be unnamed, in which case they're described by their contents, given that
+        // what follows the keyword can't be mistaken for a name
+        let is_test = matches!(
+            block_type.to_string().as_str(),
+            "it" | "test" | "xit" | "xtest" | "fit" | "ftest"
+        );
+        let is_unnamed = input.peek(Brace)
+            || input.peek(Token![=>])
+            || input.peek(Token![;])
+            || input.peek(Token![for])
+            || input.peek(keyword::forall)
+            || input.peek(Token![|]);
+        let name = if is_test && is_unnamed {
+            LitStr::new("", block_type.span())
+        } else {
+            parse_name(input)?
+        };
+        let mut timeout = None;
+        let mut retries = None;
+        let mut concurrency = None;
+        loop {
+            let content;
+            if input.parse::<Option<keyword::timeout>>()?.is_some() {
+                parenthesized!(content in input);
+                timeout = Some(parse_duration(&content)?);
+            } else if input.parse::<Option<keyword::retry>>()?.is_some() {
+                parenthesized!(content in input);
+                retries = Some(content.parse::<LitInt>()?.base10_parse::<u32>()?);
+            } else if input.parse::<Option<keyword::serial>>()?.is_some() {
+                let key = if input.peek(Paren) {
+                    parenthesized!(content in input);
+                    Some(content.parse::<LitStr>()?)
+                } else {
+                    None
+                };
+                concurrency = Some(Concurrency::new(key, 1));
+            } else if input.parse::<Option<keyword::parallel>>()?.is_some() {
+                parenthesized!(content in input);
+                let key = if content.peek(LitStr) {
+                    let key = content.parse::<LitStr>()?;
+                    content.parse::<Token![,]>()?;
+                    Some(key)
+                } else {
+                    None
+                };
+                content.parse::<keyword::max>()?;
+                content.parse::<Token![=]>()?;
+                let max = content.parse::<LitInt>()?;
+                if max.base10_parse::<u32>()? == 0 {
+                    return Err(Error::new(
+                        max.span(),
+                        "At least one test must be able to run",
+                    ));
+                }
+                concurrency = Some(Concurrency::new(key, max.base10_parse()?));
+            } else {
+                break;
+            }
+        }
+        let return_type = if input.parse::<Option<Token![->]>>()?.is_some() {
+            Some(input.parse::<Type>()?)
+        } else {
+            None
+        };
+
+        let mut block_props = BlockProps {
+            attributes,
+            is_async,
+            keyword: block_type,
+            focus,
+            description: name.value(),
+            name,
+            path: Vec::new(),
+            timeout,
+            retries,
+            concurrency,
+            return_type,
+        };
+        if let Some(reason) = skip_reason {
+            block_props.ignore(Some(&reason.value()));
+        } else if is_skipped {
+            block_props.ignore(None);
+        }
+
+        Ok(block_props)
+    }
+}
+
+/// Parses the name of a block or shared group, which must be a string literal
+fn parse_name(input: ParseStream) -> Result<LitStr> {
+    input.parse::<LitStr>().map_err(|error| {
+        Error::new(
+            error.span(),
+            "Expected a name as a string literal, e.g. `\"does something\"`",
+        )
+    })
+}
+
+/// A limit on how many tests may run at once, which is declared by `serial` or
+/// `parallel(max = <n>)` and held by the tests that share its key
+#[derive(Clone)]
+pub(crate) struct Concurrency {
+    /// The key of the shared resource, which defaults to the block the limit was declared on
+    pub(crate) key: Option<String>,
+    /// How many tests may run at once
+    pub(crate) max: u32,
+}
+
+impl Concurrency {
+    fn new(key: Option<LitStr>, max: u32) -> Self {
+        Concurrency {
+            key: key.map(|key| format!("resource {}", key.value())),
+            max,
+        }
+    }
+}
+
+/// Parses a duration, which is either a number of seconds (e.g. `2s` or `0.5s`), a number of
+/// milliseconds (e.g. `500ms`), or an expression of the type `std::time::Duration`
+fn parse_duration(input: ParseStream) -> Result<Expr> {
+    let duration = input.parse::<Expr>()?;
+    let (digits, suffix, span) = match &duration {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => (int.base10_digits(), int.suffix(), int.span()),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(float),
+            ..
+        }) => (float.base10_digits(), float.suffix(), float.span()),
+        _ => return Ok(duration),
+    };
+
+    match suffix {
+        "s" => {
+            let seconds = digits
+                .parse::<f64>()
+                .map_err(|error| Error::new(span, error))?;
+            Ok(parse_quote!(std::time::Duration::from_secs_f64(#seconds)))
+        }
+        "ms" => {
+            let milliseconds = digits
+                .parse::<f64>()
+                .map_err(|error| Error::new(span, error))?;
+            Ok(parse_quote!(std::time::Duration::from_secs_f64(#milliseconds / 1000.0)))
+        }
+        _ => Err(Error::new(
+            span,
+            "Expected a unit of either seconds or milliseconds, e.g. `2s` or `500ms`",
+        )),
+    }
+}
+
+impl BlockProps {
+    /// Makes a limit declared without a key apply to the tests within the block with the given
+    /// description
+    pub(crate) fn scope_concurrency(&mut self, scope: &str) {
+        if let Some(concurrency @ Concurrency { key: None, .. }) = &mut self.concurrency {
+            concurrency.key = Some(format!("block {}", scope));
+        }
+    }
+
+    /// The description of this block, following those of its ancestors
+    pub(crate) fn full_description(&self) -> String {
+        self.path
+            .iter()
+            .chain(std::iter::once(&self.description))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" > ")
+    }
+
+    /// Ignores the block with an `#[ignore]` attribute, replacing any existing one
+    pub(crate) fn ignore(&mut self, reason: Option<&str>) {
+        self.attributes
+            .retain(|attribute| !attribute.path.is_ident("ignore"));
+        self.attributes.push(match reason {
+            Some(reason) => parse_quote!(#[ignore = #reason]),
+            None => parse_quote!(#[ignore]),
+        });
+    }
+}
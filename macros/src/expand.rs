@@ -0,0 +1,129 @@
+//! Defines the expansion of `it_behaves_like` statements into the shared groups they refer to,
+//! which happens before the inheritance and generation of blocks
+
+use crate::block::*;
+use quote::quote;
+use syn::parse::{Error, Result};
+
+/// How deeply shared groups may include each other, which guards against recursive groups
+const MAX_DEPTH: usize = 16;
+
+impl Root {
+    /// Replaces every `it_behaves_like` statement with a `Describe` block containing the shared
+    /// group it refers to
+    pub(crate) fn expand(&mut self) -> Result<()> {
+        let scope = self.shared.iter().collect::<Vec<_>>();
+        for describe in &mut self.blocks {
+            describe.expand(&scope, 0)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Describe {
+    /// Expands the `it_behaves_like` statements within this block and its descendants, where
+    /// `scope` holds the shared groups declared by its ancestors
+    fn expand(&mut self, scope: &[&Shared], depth: usize) -> Result<()> {
+        let shared = std::mem::take(&mut self.shared);
+        let scope = scope.iter().copied().chain(&shared).collect::<Vec<_>>();
+
+        // The names of the groups included by this block, in order
+        let included = self
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::ItBehavesLike(it_behaves_like) => Some(it_behaves_like.name.value()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut position = 0;
+
+        for block in &mut self.blocks {
+            match block {
+                Block::Describe(describe) => describe.expand(&scope, depth)?,
+                Block::Test(_) => {}
+                Block::ItBehavesLike(it_behaves_like) => {
+                    if depth == MAX_DEPTH {
+                        return Err(Error::new(
+                            it_behaves_like.name.span(),
+                            "Shared groups are nested too deeply, are they including each other?",
+                        ));
+                    }
+                    let mut describe = it_behaves_like.include(&scope)?;
+
+                    // Number the groups included more than once, so that their modules are
+                    // distinct (e.g. `a_stack_1` and `a_stack_2`)
+                    let name = &included[position];
+                    if included.iter().filter(|other| *other == name).count() > 1 {
+                        let number = included[..=position]
+                            .iter()
+                            .filter(|other| *other == name)
+                            .count();
+                        let description = &mut describe.properties.block_props.description;
+                        *description = format!("{} {}", description, number);
+                    }
+                    position += 1;
+
+                    describe.expand(&scope, depth + 1)?;
+
+                    *block = Block::Describe(describe);
+                }
+            }
+        }
+
+        self.shared = shared;
+        Ok(())
+    }
+}
+
+impl ItBehavesLike {
+    /// Creates the `Describe` block for the shared group this statement refers to, declaring its
+    /// parameters as `let` bindings of the given arguments
+    fn include(&self, scope: &[&Shared]) -> Result<Describe> {
+        let name = self.name.value();
+        let shared = scope
+            .iter()
+            .rev()
+            .find(|shared| shared.name.value() == name)
+            .ok_or_else(|| {
+                Error::new(
+                    self.name.span(),
+                    format!("No shared group named \"{}\" is in scope", name),
+                )
+            })?;
+
+        if shared.params.len() != self.args.len() {
+            return Err(Error::new(
+                self.name.span(),
+                format!(
+                    "The shared group \"{}\" expects {} argument(s), but {} were given",
+                    name,
+                    shared.params.len(),
+                    self.args.len()
+                ),
+            ));
+        }
+
+        let mut describe = shared.describe.clone();
+        describe.properties.block_props.name = self.name.clone();
+        let mut params = Vec::new();
+        for (Param { name, ty }, arg) in shared.params.iter().zip(&self.args) {
+            // Declarations within the group itself take precedence over its parameters
+            if describe
+                .properties
+                .lets
+                .iter()
+                .any(|other| other.name == *name)
+            {
+                continue;
+            }
+
+            let ty = ty.as_ref().map(|ty| quote!(: #ty));
+            params.push(syn::parse2::<Let>(quote!(let #name #ty = #arg;))?);
+        }
+        describe.properties.lets.splice(0..0, params);
+
+        Ok(describe)
+    }
+}
@@ -0,0 +1,48 @@
+//! Defines the check that each fixture requested by a test is declared by one of its ancestoral
+//! `Describe` blocks, which happens before the generation of blocks
+
+use crate::block::*;
+use syn::parse::{Error, Result};
+use syn::Ident;
+
+impl Root {
+    /// Checks that each parameter of a test names a fixture that it inherits
+    pub(crate) fn check_fixtures(&self) -> Result<()> {
+        let mut errors = Errors::default();
+        for describe in &self.blocks {
+            describe.check_fixtures(&[], &mut errors);
+        }
+
+        errors.finish()
+    }
+}
+
+impl Describe {
+    /// Checks the parameters of the tests within this block and its descendants, given the names
+    /// of the fixtures declared by its ancestors
+    fn check_fixtures(&self, declared: &[&Ident], errors: &mut Errors) {
+        let mut declared = declared.to_vec();
+        declared.extend(self.properties.fixtures.iter().map(|fixture| &fixture.name));
+
+        for block in &self.blocks {
+            match block {
+                Block::Describe(describe) => describe.check_fixtures(&declared, errors),
+                Block::Test(test) => {
+                    for Param { name, .. } in &test.params {
+                        if !declared.contains(&name) {
+                            errors.push(Error::new(
+                                name.span(),
+                                format!(
+                                    "No fixture named `{}` is declared for this test, e.g. \
+                                     `fixture {}: Type = value;`",
+                                    name, name
+                                ),
+                            ));
+                        }
+                    }
+                }
+                Block::ItBehavesLike(_) => {}
+            }
+        }
+    }
+}
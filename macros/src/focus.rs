@@ -0,0 +1,89 @@
+//! Defines focus mode, where only the tests within `fdescribe`/`fcontext`/`fit`/`ftest` blocks are
+//! run, which happens before the inheritance and generation of blocks
+
+use crate::block::*;
+use syn::parse::{Error, Result};
+use syn::Ident;
+
+/// The environment variable which, when set to anything but an empty string, `false` or `0`,
+/// forbids focused blocks from being committed
+const CI_VARIABLE: &str = "CI";
+
+impl Root {
+    /// Ignores every test outside of a focused block, given that any block is focused
+    pub(crate) fn focus(&mut self) -> Result<()> {
+        let focus = match self.blocks.iter().find_map(Describe::find_focus) {
+            Some(focus) => focus.clone(),
+            None => return Ok(()),
+        };
+
+        if is_ci() {
+            return Err(Error::new(
+                focus.span(),
+                format!(
+                    "`{}` only runs focused tests, remove it before committing (the `{}` \
+                     environment variable is set)",
+                    focus, CI_VARIABLE
+                ),
+            ));
+        }
+
+        for describe in &mut self.blocks {
+            describe.unfocus();
+        }
+        self.is_focused = true;
+
+        Ok(())
+    }
+}
+
+/// Whether the macro is expanded on CI, according to the `CI` environment variable
+fn is_ci() -> bool {
+    std::env::var_os(CI_VARIABLE).is_some_and(|ci| !ci.is_empty() && ci != "false" && ci != "0")
+}
+
+impl Describe {
+    /// Finds the keyword of the first focused block within this block, including itself
+    fn find_focus(&self) -> Option<&Ident> {
+        self.properties.block_props.focus.as_ref().or_else(|| {
+            self.blocks.iter().find_map(|block| match block {
+                Block::Describe(describe) => describe.find_focus(),
+                Block::Test(test) => test.properties.focus.as_ref(),
+                Block::ItBehavesLike(_) => None,
+            })
+        })
+    }
+
+    /// Ignores this block unless it's focused or contains a focused block, in which case only
+    /// the unfocused blocks it contains are ignored
+    fn unfocus(&mut self) {
+        if self.properties.block_props.focus.is_some() {
+            return;
+        }
+        if self.find_focus().is_none() {
+            self.properties.block_props.unfocus();
+            return;
+        }
+
+        for block in &mut self.blocks {
+            match block {
+                Block::Describe(describe) => describe.unfocus(),
+                Block::Test(test) if test.properties.focus.is_none() => test.properties.unfocus(),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl BlockProps {
+    /// Ignores this unfocused block, unless it's already ignored for another reason
+    fn unfocus(&mut self) {
+        let is_ignored = self
+            .attributes
+            .iter()
+            .any(|attribute| attribute.path.is_ident("ignore"));
+        if !is_ignored {
+            self.ignore(Some("not focused"));
+        }
+    }
+}
@@ -0,0 +1,1039 @@
+//! Defines the code translations for the various macro components
+
+use crate::block::*;
+use crate::ident::{ident_name, module_name};
+use crate::inherit::Inherit;
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Attribute, Expr, LitStr, Type, TypeReference};
+
+/// The trait and respective function for generating the corresponding code translations
+pub(crate) trait Generate {
+    fn generate(&mut self, parent_props: Option<&DescribeProps>) -> TokenStream;
+}
+
+/// Generates the root `Describe` blocks within the macro, adding the `#[cfg(test)]` outer
+/// attribute to each
+impl Generate for Root {
+    fn generate(&mut self, _parent_props: Option<&DescribeProps>) -> TokenStream {
+        // Reading the `CI` variable through `option_env!` has the tests rebuilt once it changes, so
+        // that focused blocks fail to compile on CI even if they were compiled beforehand
+        let tracked_env = if self.is_focused {
+            quote! {
+                #[cfg(test)]
+                const _: Option<&str> = option_env!("CI");
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        let root_blocks = self
+            .blocks
+            .iter_mut()
+            .map(|block| {
+                let root_block = block.generate(None);
+                quote! {
+                    #[cfg(test)]
+                    #root_block
+                }
+            })
+            .collect::<TokenStream>();
+
+        quote! {
+            #tracked_env
+            #root_blocks
+        }
+    }
+}
+
+/// Determines the respective generate function to call for each block type
+impl Generate for Block {
+    fn generate(&mut self, parent_props: Option<&DescribeProps>) -> TokenStream {
+        match self {
+            Block::Test(test) => test.generate(parent_props),
+            Block::Describe(describe) => describe.generate(parent_props),
+            Block::ItBehavesLike(_) => unreachable!("`it_behaves_like` is expanded beforehand"),
+        }
+    }
+}
+
+/// Generates a `mod` block with inherited properties
+impl Generate for Describe {
+    fn generate(&mut self, parent_props: Option<&DescribeProps>) -> TokenStream {
+        let prelude = if parent_props.is_some() {
+            quote!(
+                use super::*;
+            )
+        } else {
+            TokenStream::new()
+        };
+
+        // Generate a module for each instance of a parameterized block, within a module named
+        // after the block
+        if let Some(instances) = self.instances.take() {
+            let ident = self.module_ident();
+            let modules = instances
+                .generate_items()
+                .into_iter()
+                .map(|(name, item)| {
+                    // Each instance is described by its name, following that of the block
+                    let mut instance = self.clone();
+                    let block =
+                        std::mem::replace(&mut instance.properties.block_props.description, name);
+                    let module = instance.generate_module(
+                        parent_props,
+                        Some(block),
+                        quote! {
+                            #prelude
+
+                            #[allow(dead_code)]
+                            #item
+                        },
+                    );
+
+                    // The `use` statements may only be needed by some of the instances
+                    quote! {
+                        #[allow(unused_imports)]
+                        #module
+                    }
+                })
+                .collect::<TokenStream>();
+
+            // The instances reach the parent's items through this module
+            let mod_token = quote_spanned!(self.properties.block_props.keyword.span()=> mod);
+            return quote! {
+                #mod_token #ident {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #modules
+                }
+            };
+        }
+
+        self.generate_module(parent_props, None, prelude)
+    }
+}
+
+impl Describe {
+    /// The name of the module generated for this block
+    pub(crate) fn module_name(&self) -> String {
+        module_name(
+            &self.properties.block_props.description,
+            &self.module_prefix,
+        )
+    }
+
+    /// The ident of the module generated for this block, spanned to its name
+    fn module_ident(&self) -> Ident {
+        Ident::new(&self.module_name(), self.properties.block_props.name.span())
+    }
+
+    /// Generates the `mod` block for this block, starting with the given `prelude` items, which is
+    /// an instance of the parameterized block with the description `instance_of` if given
+    fn generate_module(
+        &mut self,
+        parent_props: Option<&DescribeProps>,
+        instance_of: Option<String>,
+        prelude: TokenStream,
+    ) -> TokenStream {
+        // Generate corresponding `use` statements
+        let uses = self
+            .properties
+            .uses
+            .iter()
+            .map(|use_tree| quote!(use #use_tree;))
+            .collect::<TokenStream>();
+
+        // Inherit parent's `DescribeProps`
+        let inherited_hooks = parent_props.map_or(0, |parent_props| parent_props.once_hooks.len());
+        if let Some(parent_props) = parent_props {
+            self.inherit(parent_props);
+        }
+        let block_props = &mut self.properties.block_props;
+        block_props.path.extend(instance_of);
+        block_props.scope_concurrency(&block_props.full_description());
+
+        // Generate the items backing this block's own `before_all`/`after_all` blocks
+        let once_hooks = &self.properties.once_hooks;
+        let hook_items = once_hooks
+            .get(inherited_hooks)
+            .map(|hooks| {
+                let is_ignored = is_ignored(&self.properties.block_props);
+                let tests = self
+                    .blocks
+                    .iter()
+                    .flat_map(|block| block.test_names(is_ignored))
+                    .collect::<Vec<_>>();
+                hooks.generate_items(once_hooks, &tests)
+            })
+            .unwrap_or_default();
+
+        // Generate corresponding subblocks
+        let cloned_props = self.properties.clone();
+        let blocks = self
+            .blocks
+            .iter_mut()
+            .map(|block| block.generate(Some(&cloned_props)))
+            .collect::<TokenStream>();
+
+        // Assign module ident based on name
+        let ident = self.module_ident();
+        let mod_token = quote_spanned!(self.properties.block_props.keyword.span()=> mod);
+
+        quote! {
+            #mod_token #ident {
+                #uses
+
+                #prelude
+
+                #hook_items
+
+                #blocks
+            }
+        }
+    }
+}
+
+/// Generates a unit test with inherited properties, or one unit test per case if the test is
+/// parameterized
+impl Generate for Test {
+    fn generate(&mut self, parent_props: Option<&DescribeProps>) -> TokenStream {
+        // Inherit parent's `BlockProps` and `before`/`after` code sequences
+        if let Some(parent_props) = parent_props {
+            self.inherit(parent_props);
+        }
+
+        let idents = self.fn_names();
+
+        if let Some(Cases { pattern, values }) = &self.cases {
+            values
+                .iter()
+                .zip(idents)
+                .map(|(value, ident)| {
+                    self.generate_fn(
+                        &Ident::new(&ident, self.properties.name.span()),
+                        quote!(let #pattern = #value;),
+                    )
+                })
+                .collect()
+        } else {
+            self.generate_fn(
+                &Ident::new(&idents[0], self.properties.name.span()),
+                TokenStream::new(),
+            )
+        }
+    }
+}
+
+impl Test {
+    /// The names of the functions generated for this test, one for each case if it's
+    /// parameterized
+    pub(crate) fn fn_names(&self) -> Vec<String> {
+        let name = &self.properties.description;
+        match &self.cases {
+            Some(Cases { values, .. }) => case_idents(name, values),
+            None => vec![ident_name(name)],
+        }
+    }
+
+    /// The `let` declarations inherited by this test, along with the `subject` and `is_expected`
+    /// declarations of its `subject` block unless they're overridden
+    fn subject_lets(&self) -> Vec<Let> {
+        let mut lets = self.lets.clone();
+        if let Some(BasicBlock(subject, span)) = &self.subject {
+            let subject = braces(quote!(#(#subject)*), *span);
+            let declarations: [Let; 2] = [
+                parse_quote!(let subject = #subject;),
+                parse_quote!(let is_expected = expect(subject);),
+            ];
+            for declaration in declarations {
+                if !lets.iter().any(|other| other.name == declaration.name) {
+                    lets.push(declaration);
+                }
+            }
+        }
+        lets
+    }
+
+    /// Generates a single test function with the given ident, placing the `bindings` between the
+    /// inherited `before` code sequence and the test's contents
+    fn generate_fn(&self, ident: &Ident, bindings: TokenStream) -> TokenStream {
+        let BlockProps {
+            attributes,
+            keyword,
+            is_async,
+            timeout,
+            retries,
+            concurrency,
+            return_type,
+            ..
+        } = &self.properties;
+        let before = &self.before;
+        let BasicBlock(content, content_span) = &self.content;
+
+        // Errors about the signature point at the keyword the test was declared with
+        let fn_token = quote_spanned!(keyword.span()=> fn);
+
+        // Hold a guard for each `after_all` block, innermost first, which counts this test as
+        // finished once dropped
+        let guards = self
+            .once_hooks
+            .iter()
+            .rev()
+            .filter(|hooks| hooks.after_all.is_some())
+            .map(|hooks| once_ident("__AfterAll", hooks.index))
+            .collect::<Vec<_>>();
+        let guards = if guards.is_empty() {
+            None
+        } else {
+            Some(quote!(let __after_all = (#(#guards,)*);))
+        };
+
+        // Borrow the values shared by each `before_all` block
+        let shared = shared_bindings(&self.once_hooks);
+
+        // Build the fixtures requested by this test, along with those they depend on, and bind
+        // them to its parameters
+        let fixtures = self
+            .fixtures
+            .iter()
+            .map(|Fixture { name, ty, value }| parse_quote!(let mut #name: #ty = #value;))
+            .collect::<Vec<Let>>();
+        let names = self.params.iter().map(|Param { name, .. }| name);
+        let fixtures = used_lets(&fixtures, quote!(#(#names)*))
+            .into_iter()
+            .map(|Let { local, .. }| {
+                quote! {
+                    #[allow(unused_mut)]
+                    #local
+                }
+            })
+            .collect::<Vec<_>>();
+        let params = self.params.iter().map(|Param { name, ty }| match ty {
+            Some(Type::Reference(TypeReference {
+                mutability: Some(_),
+                ..
+            })) => quote!(let #name: #ty = &mut #name;),
+            Some(Type::Reference(_)) => quote!(let #name: #ty = &#name;),
+            Some(ty) => quote!(let #name: #ty = #name;),
+            None => quote!(let #name = &#name;),
+        });
+
+        // Collect the code which may refer to `let` bindings or take snapshots
+        let mut used_tokens = quote!(#(#fixtures)* #(#before)* #bindings #(#content)*);
+        if let Some(after) = &self.after {
+            used_tokens.extend(after.content.0.iter().map(|stmt| quote!(#stmt)));
+        }
+        let uses_snapshots = idents(used_tokens.clone())
+            .iter()
+            .any(|ident| ident == "expect_snapshot");
+
+        // Declare the `let` bindings used by this test, along with those they depend on, after the
+        // `before` code sequence unless it or a fixture uses them
+        let lets = self.subject_lets();
+        let early = used_lets(&lets, quote!(#(#fixtures)* #(#before)*));
+        let (early_lets, late_lets): (Vec<_>, Vec<_>) = used_lets(&lets, used_tokens.clone())
+            .into_iter()
+            .partition(|declaration| early.iter().any(|other| other.name == declaration.name));
+        let [early_lets, late_lets] = [early_lets, late_lets].map(|lets| {
+            lets.into_iter()
+                .map(|Let { local, .. }| {
+                    quote! {
+                        #[allow(unused_variables)]
+                        #local
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        let uses_expectations = calls(
+            quote!(#(#early_lets)* #(#late_lets)* #used_tokens),
+            "expect",
+        );
+
+        // `async` tests without a runtime's test attribute are driven by the bundled executor
+        let uses_executor = *is_async && !has_test_attribute(attributes);
+
+        // Generate the outer attributes and optional `async` token for this test
+        let (attr_tokens, async_token) = if *is_async && !uses_executor {
+            (quote!(#(#attributes)*), Some(quote!(async)))
+        } else {
+            (
+                quote_spanned! {keyword.span()=>
+                    #[test]
+                    #(#attributes)*
+                },
+                None,
+            )
+        };
+
+        // Pending tests are ignored, so they don't need anything but their attributes
+        if self.is_pending {
+            return quote! {
+                #attr_tokens
+                #async_token #fn_token #ident() {}
+            };
+        }
+
+        // Generate the test with or without a return type
+        let output = return_type
+            .as_ref()
+            .map(|return_type| quote!(-> #return_type));
+        let output_type = return_type
+            .as_ref()
+            .map_or_else(|| quote!(()), |return_type| quote!(#return_type));
+
+        // Run the `after` code sequence on every exit path of the test's contents
+        let content = if let Some(After {
+            uses_outcome,
+            content: BasicBlock(after, after_span),
+        }) = &self.after
+        {
+            let result = if *is_async {
+                quote! {
+                    let __result = ::demonstrate::__private::catch_unwind::<#output_type, _>(async {
+                        #(#content)*
+                    })
+                    .await;
+                }
+            } else {
+                quote! {
+                    let __result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(
+                        || #output {
+                            #(#content)*
+                        },
+                    ));
+                }
+            };
+
+            let outcome = if *uses_outcome {
+                Some(quote!(let __outcome = ::demonstrate::__private::Outcome::new(&__result);))
+            } else {
+                None
+            };
+
+            let after = braces(quote!(#outcome #(#after)*), *after_span);
+
+            quote! {
+                #result
+
+                #after
+
+                match __result {
+                    Ok(result) => result,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+        } else {
+            quote!(#(#content)*)
+        };
+
+        // Declare the snapshots taken by `expect_snapshot!`, which are named after the test's
+        // module and function, and taken from the first again on each run of the test
+        let description = self.properties.full_description();
+        let snapshots = if uses_snapshots {
+            let test = ident.to_string();
+            Some(quote! {
+                static __SNAPSHOTS: ::demonstrate::__private::Snapshots = ::demonstrate::__private::Snapshots::new(
+                    env!("CARGO_MANIFEST_DIR"),
+                    module_path!(),
+                    #test,
+                    #description,
+                );
+                __SNAPSHOTS.reset();
+            })
+        } else {
+            None
+        };
+
+        // Shadow `expect` with a function that includes the test's description in the failure
+        // messages of its expectations
+        let expectations = if uses_expectations {
+            Some(quote! {
+                fn expect<T: std::fmt::Debug>(
+                    actual: T,
+                ) -> ::demonstrate::__private::Expectation<T> {
+                    ::demonstrate::__private::Expectation::new(actual, #description)
+                }
+            })
+        } else {
+            None
+        };
+
+        // Errors about the test's body, such as a mismatched return type, point at its contents
+        let body = braces(
+            quote! {
+                #snapshots
+                #expectations
+                #shared
+                #(#early_lets)*
+                #(#fixtures)*
+                #(#params)*
+                #(#before)*
+                #(#late_lets)*
+                #bindings
+                #content
+            },
+            *content_span,
+        );
+
+        // Tests with a timeout run on a separate thread, which only sends back their error
+        let fn_output = match return_type {
+            Some(return_type) if timeout.is_some() && !*is_async => Some(quote! {
+                -> <#return_type as ::demonstrate::__private::Timed>::Output
+            }),
+            _ => output.clone(),
+        };
+
+        // Run each attempt at the test within its `around` blocks, outermost first
+        let body = self.around.iter().rev().fold(body, |body, around| {
+            wrap_around(around, body, *is_async, &output, &description)
+        });
+
+        // Fail each attempt at the test once it exceeds its time limit, by running it on a
+        // separate thread or polling it until its deadline, and make another attempt at failed
+        // tests while they have retries left
+        let body = if *is_async {
+            // Run a property test for each of its generated inputs
+            let mut future = match &self.forall {
+                Some(Forall { names, strategies }) => quote! {
+                    ::demonstrate::__private::forall_async::<#output_type, _, _, _>(
+                        #description,
+                        &[#(stringify!(#names)),*],
+                        (#(#strategies,)*),
+                        |(#(#names,)*)| async move #body,
+                    )
+                },
+                None => quote!(async move #body),
+            };
+            if let Some(timeout) = timeout {
+                future = quote! {
+                    ::demonstrate::__private::deadline::<#output_type, _>(#timeout, #description, #future)
+                };
+            }
+            if let Some(retries) = retries {
+                future = quote! {
+                    ::demonstrate::__private::retry_async::<#output_type, _, _>(
+                        #retries,
+                        #description,
+                        || #future,
+                    )
+                };
+            }
+            if self.forall.is_some() || timeout.is_some() || retries.is_some() {
+                quote!({ #future.await })
+            } else {
+                body
+            }
+        } else {
+            let mut test = match &self.forall {
+                Some(Forall { names, strategies }) => quote!({
+                    ::demonstrate::__private::forall(
+                        #description,
+                        &[#(stringify!(#names)),*],
+                        (#(#strategies,)*),
+                        |(#(#names,)*)| #output #body,
+                    )
+                }),
+                None => body,
+            };
+            if let Some(timeout) = timeout {
+                test = quote!({
+                    ::demonstrate::__private::timeout(#timeout, #description, move || -> #output_type #test)
+                });
+            }
+            if let Some(retries) = retries {
+                test = quote!({
+                    ::demonstrate::__private::retry(#retries, #description, || #fn_output #test)
+                });
+            }
+            test
+        };
+
+        // Wait until the test may run alongside the others sharing its limit, holding a permit
+        // until it's finished
+        let permit = concurrency.as_ref().map(
+            |Concurrency { key, max }| quote!(let __permit = ::demonstrate::__private::acquire(#key, #max);),
+        );
+
+        // The guards are held outside of the attempts, so that the test is only counted as
+        // finished once
+        let body = prepend(quote!(#permit #guards), body);
+
+        if uses_executor {
+            return quote! {
+                #attr_tokens
+                #fn_token #ident() #output {
+                    async fn __test() #output #body
+                    ::demonstrate::__private::block_on(__test())
+                }
+            };
+        }
+
+        quote! {
+            #attr_tokens
+            #async_token #fn_token #ident() #fn_output #body
+        }
+    }
+}
+
+/// Finds the `let` declarations whose names are referenced within `tokens`, directly or through
+/// other `let` declarations, ordered so that each follows the declarations it depends on
+fn used_lets(lets: &[Let], tokens: TokenStream) -> Vec<&Let> {
+    fn visit<'a>(
+        lets: &'a [Let],
+        name: &Ident,
+        visiting: &mut Vec<Ident>,
+        used: &mut Vec<&'a Let>,
+    ) {
+        let declaration = match lets.iter().find(|declaration| declaration.name == *name) {
+            Some(declaration) => declaration,
+            None => return,
+        };
+        if visiting.contains(name) || used.iter().any(|used| used.name == *name) {
+            return;
+        }
+
+        visiting.push(name.clone());
+        if let Some((_, value)) = &declaration.local.init {
+            for dependency in idents(quote!(#value)) {
+                visit(lets, &dependency, visiting, used);
+            }
+        }
+        visiting.pop();
+
+        used.push(declaration);
+    }
+
+    let mut used = Vec::new();
+    for name in idents(tokens) {
+        visit(lets, &name, &mut Vec::new(), &mut used);
+    }
+
+    used
+}
+
+/// Collects every identifier within `tokens`, including those captured by the format strings of
+/// macro invocations (e.g. `area` in `format!("{area}")`)
+fn idents(tokens: TokenStream) -> Vec<Ident> {
+    let mut found = Vec::new();
+    let mut is_macro = false;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => found.push(ident),
+            TokenTree::Group(group) => {
+                if is_macro {
+                    found.extend(format_idents(group.stream()));
+                }
+                found.extend(idents(group.stream()));
+            }
+            TokenTree::Punct(ref punct) if punct.as_char() == '!' => {
+                is_macro = true;
+                continue;
+            }
+            _ => {}
+        }
+        is_macro = false;
+    }
+
+    found
+}
+
+/// Collects the identifiers captured by the string literals among the arguments of a macro, which
+/// are named within braces (e.g. `{area}` or `{area:>width$}`)
+fn format_idents(tokens: TokenStream) -> Vec<Ident> {
+    let is_ident = |name: &str| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+
+    let mut idents = Vec::new();
+    for token in tokens {
+        let literal = match token {
+            TokenTree::Literal(literal) => literal,
+            _ => continue,
+        };
+        let span = literal.span();
+        let format = match syn::parse2::<LitStr>(TokenTree::Literal(literal).into()) {
+            Ok(format) => format.value(),
+            Err(_) => continue,
+        };
+
+        // Escaped braces can't start a placeholder
+        for placeholder in format.replace("{{", "").split('{').skip(1) {
+            let placeholder = match placeholder.find('}') {
+                Some(end) => &placeholder[..end],
+                None => continue,
+            };
+            let (argument, spec) =
+                placeholder.split_at(placeholder.find(':').unwrap_or(placeholder.len()));
+            let names = spec
+                .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '$')
+                .filter_map(|part| part.strip_suffix('$'));
+            for name in std::iter::once(argument).chain(names) {
+                if is_ident(name) {
+                    idents.push(Ident::new(name, span));
+                }
+            }
+        }
+    }
+
+    idents
+}
+
+/// Wraps the body of a test within an `around` block, whose closure runs the body and stores its
+/// result to be returned once the `around` block is finished
+fn wrap_around(
+    around: &Around,
+    body: TokenStream,
+    is_async: bool,
+    output: &Option<TokenStream>,
+    description: &str,
+) -> TokenStream {
+    let Around {
+        run,
+        is_async: is_async_around,
+        content: BasicBlock(content, content_span),
+    } = around;
+
+    let run_body = match (is_async, is_async_around) {
+        (true, true) => quote!(async move { __result.set(Some(async move #body.await)) }),
+        // Synchronous `around` blocks of `async` tests block on them
+        (true, false) => {
+            quote!(__result.set(Some(::demonstrate::__private::block_on(async move #body))))
+        }
+        (false, _) => quote!(__result.set(Some((move || #output #body)()))),
+    };
+    let content = braces(quote!(#(#content)*), *content_span);
+
+    quote!({
+        let __output = std::cell::Cell::new(None);
+        {
+            let __result = &__output;
+            let #run = move || #run_body;
+            #content
+        }
+        ::demonstrate::__private::ran_around(__output.into_inner(), #description)
+    })
+}
+
+/// Whether `tokens` call a function of the given name, as opposed to a method of that name
+fn calls(tokens: TokenStream, name: &str) -> bool {
+    let mut is_method = false;
+    for token in tokens {
+        match &token {
+            TokenTree::Ident(ident) if ident == name && !is_method => return true,
+            TokenTree::Group(group) if calls(group.stream(), name) => return true,
+            _ => {}
+        }
+        is_method = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '.');
+    }
+    false
+}
+
+impl Instances {
+    /// Names the module of each instance after its type (e.g. `vec_u8`) or its constant (e.g.
+    /// `n_1`), pairing it with the item declaring that type or constant
+    fn generate_items(&self) -> Vec<(String, TokenStream)> {
+        let (name, values, items): (_, Vec<_>, Vec<_>) = match self {
+            Instances::Types { name, types } => (
+                name,
+                types
+                    .iter()
+                    .map(|ty| module_name(&quote!(#ty).to_string(), ""))
+                    .collect(),
+                types.iter().map(|ty| quote!(type #name = #ty;)).collect(),
+            ),
+            Instances::Consts { name, ty, values } => (
+                name,
+                values
+                    .iter()
+                    .map(|value| module_name(&format!("{} {}", name, quote!(#value)), ""))
+                    .collect(),
+                values
+                    .iter()
+                    .map(|value| quote!(const #name: #ty = #value;))
+                    .collect(),
+            ),
+        };
+
+        // Fall back to the position of each instance if the names aren't valid or distinct
+        let is_distinct = values.iter().enumerate().all(|(index, value)| {
+            value.starts_with(|c: char| c.is_ascii_alphabetic()) && !values[..index].contains(value)
+        });
+        let names = if is_distinct {
+            values
+        } else {
+            (1..=items.len())
+                .map(|number| module_name(&format!("{} {}", name, number), ""))
+                .collect()
+        };
+
+        names.into_iter().zip(items).collect()
+    }
+}
+
+/// Names each case of a parameterized test after its value (e.g. `adds_with_1_2_3`), falling back
+/// to the case's position (e.g. `adds_case_1`) if the values don't produce distinct names
+fn case_idents(name: &str, values: &[Expr]) -> Vec<String> {
+    let name = ident_name(name);
+    let idents = values
+        .iter()
+        .map(|value| ident_name(&format!("{} with {}", name, quote!(#value))))
+        .collect::<Vec<_>>();
+
+    let prefix = ident_name(&format!("{} with", name));
+    let is_distinct = idents
+        .iter()
+        .enumerate()
+        .all(|(index, ident)| ident.len() > prefix.len() && !idents[..index].contains(ident));
+
+    if is_distinct {
+        idents
+    } else {
+        (1..=values.len())
+            .map(|number| ident_name(&format!("{} case {}", name, number)))
+            .collect()
+    }
+}
+
+impl Block {
+    /// Names the tests generated for this block relative to the module of its parent, pairing
+    /// each with whether it's ignored, which it is within an ignored ancestor
+    fn test_names(&self, is_ignored: bool) -> Vec<(String, bool)> {
+        match self {
+            Block::Test(test) => {
+                let is_ignored = is_ignored || self::is_ignored(&test.properties);
+                test.fn_names()
+                    .into_iter()
+                    .map(|name| (name, is_ignored))
+                    .collect()
+            }
+            Block::Describe(describe) => {
+                let is_ignored = is_ignored || self::is_ignored(&describe.properties.block_props);
+                let names = describe
+                    .blocks
+                    .iter()
+                    .flat_map(|block| block.test_names(is_ignored))
+                    .collect::<Vec<_>>();
+                let modules = match &describe.instances {
+                    Some(instances) => instances
+                        .generate_items()
+                        .into_iter()
+                        .map(|(name, _)| format!("{}::{}", describe.module_name(), name))
+                        .collect(),
+                    None => vec![describe.module_name()],
+                };
+                modules
+                    .iter()
+                    .flat_map(|module| {
+                        names.iter().map(move |(name, is_ignored)| {
+                            (format!("{}::{}", module, name), *is_ignored)
+                        })
+                    })
+                    .collect()
+            }
+            Block::ItBehavesLike(_) => unreachable!("`it_behaves_like` is expanded beforehand"),
+        }
+    }
+}
+
+/// Whether the block has an `#[ignore]` attribute
+fn is_ignored(props: &BlockProps) -> bool {
+    props
+        .attributes
+        .iter()
+        .any(|attribute| attribute.path.is_ident("ignore"))
+}
+
+impl OnceHooks {
+    /// Generates the `static` holding the values of the `before_all` block and the guard running
+    /// the `after_all` block once the last of the descendant `tests` that the test harness runs
+    /// has finished
+    ///
+    /// `once_hooks` contains these hooks and those of every ancestor, whose `before_all` values are
+    /// available to the `after_all` block. `tests` are named relative to the module of the block
+    /// and paired with whether they're ignored.
+    fn generate_items(&self, once_hooks: &[OnceHooks], tests: &[(String, bool)]) -> TokenStream {
+        let mut items = TokenStream::new();
+
+        if let Some(BeforeAll { stmts, bindings }) = &self.before_all {
+            // The values of ancestoral `before_all` blocks are available during initialization
+            let shared = shared_bindings(&once_hooks[..self.index]);
+            let cell = once_ident("__BEFORE_ALL_", self.index);
+            let accessor = once_ident("__before_all_", self.index);
+            let names = bindings.iter().map(|(name, _)| name);
+            let types = bindings.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+
+            if self.is_async {
+                // The initialization may `.await`, so it can't happen within a lock
+                items.extend(quote! {
+                    static #cell: ::demonstrate::__private::OnceAsync<(#(#types,)*)> =
+                        ::demonstrate::__private::OnceAsync::new();
+
+                    async fn #accessor() -> &'static (#(#types,)*) {
+                        #cell
+                            .get_or_init(async {
+                                #shared
+                                #(#stmts)*
+                                (#(#names,)*)
+                            })
+                            .await
+                    }
+                });
+            } else {
+                items.extend(quote! {
+                    static #cell: std::sync::OnceLock<(#(#types,)*)> = std::sync::OnceLock::new();
+
+                    fn #accessor() -> &'static (#(#types,)*) {
+                        #cell.get_or_init(|| {
+                            #shared
+                            #(#stmts)*
+                            (#(#names,)*)
+                        })
+                    }
+                });
+            }
+        }
+
+        if let Some(BasicBlock(stmts, _)) = &self.after_all {
+            let counter = once_ident("__AFTER_ALL_", self.index);
+            let runner = once_ident("__after_all_", self.index);
+            let guard = once_ident("__AfterAll", self.index);
+            let names = tests.iter().map(|(name, _)| name);
+            let ignored = tests.iter().map(|(_, is_ignored)| is_ignored);
+
+            // Borrow the `before_all` values of these hooks and every ancestor's, given that they
+            // were initialized
+            let shared = once_hooks[..=self.index]
+                .iter()
+                .filter_map(|hooks| {
+                    let names = hooks
+                        .before_all
+                        .as_ref()?
+                        .bindings
+                        .iter()
+                        .map(|(name, _)| name);
+                    Some((
+                        once_ident("__BEFORE_ALL_", hooks.index),
+                        once_ident("values_", hooks.index),
+                        quote!((#(ref #names,)*)),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            let body = if shared.is_empty() {
+                quote!(#(#stmts)*)
+            } else {
+                let cells = shared.iter().map(|(cell, _, _)| cell);
+                let values = shared
+                    .iter()
+                    .map(|(_, values, _)| values)
+                    .collect::<Vec<_>>();
+                let patterns = shared.iter().map(|(_, _, pattern)| pattern);
+                quote! {
+                    if let (#(Some(#values),)*) = (#(#cells.get(),)*) {
+                        #(let #patterns = *#values;)*
+                        #(#stmts)*
+                    }
+                }
+            };
+
+            items.extend(quote! {
+                static #counter: std::sync::atomic::AtomicUsize =
+                    std::sync::atomic::AtomicUsize::new(0);
+
+                #[allow(unused_variables)]
+                fn #runner() {
+                    #body
+                }
+
+                struct #guard;
+
+                impl Drop for #guard {
+                    fn drop(&mut self) {
+                        // Only the tests that the test harness runs are waited for, which depend on
+                        // its arguments
+                        let finished = #counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                        let tests = &[#((#names, #ignored)),*];
+                        if finished == ::demonstrate::__private::selected(module_path!(), tests) {
+                            if std::thread::panicking() {
+                                // Panicking again would abort the test binary
+                                let _ = std::panic::catch_unwind(#runner);
+                            } else {
+                                #runner();
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        items
+    }
+}
+
+/// Borrows the values of each `before_all` block in `once_hooks`, outermost first
+fn shared_bindings(once_hooks: &[OnceHooks]) -> TokenStream {
+    once_hooks
+        .iter()
+        .filter_map(|hooks| {
+            let names = hooks
+                .before_all
+                .as_ref()?
+                .bindings
+                .iter()
+                .map(|(name, _)| name);
+            let accessor = once_ident("__before_all_", hooks.index);
+            let await_token = if hooks.is_async {
+                Some(quote!(.await))
+            } else {
+                None
+            };
+
+            Some(quote! {
+                #[allow(unused_variables)]
+                let (#(ref #names,)*) = *#accessor()#await_token;
+            })
+        })
+        .collect()
+}
+
+/// Names an item generated for the `before_all`/`after_all` hooks at the given index
+fn once_ident(prefix: &str, index: usize) -> Ident {
+    Ident::new(&format!("{}{}", prefix, index), Span::call_site())
+}
+
+/// Whether the attributes include a test attribute, such as `#[async_std::test]`, which is
+/// assumed to be provided by an async runtime when applied to `async` tests
+fn has_test_attribute(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|attribute| {
+        attribute
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "test")
+    })
+}
+
+/// Wraps the tokens in curly braces with the given span
+fn braces(tokens: TokenStream, span: Span) -> TokenStream {
+    let mut group = Group::new(Delimiter::Brace, tokens);
+    group.set_span(span);
+    TokenTree::Group(group).into()
+}
+
+/// Inserts `stmts` at the start of the braced `block`, rather than wrapping it in another block
+fn prepend(stmts: TokenStream, block: TokenStream) -> TokenStream {
+    if stmts.is_empty() {
+        return block;
+    }
+
+    let mut tokens = block.clone().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::Brace => {
+            let stream = group.stream();
+            braces(quote!(#stmts #stream), group.span())
+        }
+        _ => quote!({ #stmts #block }),
+    }
+}
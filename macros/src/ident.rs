@@ -0,0 +1,72 @@
+//! Defines how the names of blocks are converted into the identifiers of their generated items
+
+use voca_rs::case::snake_case;
+use voca_rs::manipulate::latinise;
+
+/// The name used for blocks whose names contain no letters or digits
+const UNNAMED: &str = "unnamed";
+
+/// The keywords that can't be used as identifiers, in any edition
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The crates that are always in scope, along with the conventional name of test modules, which
+/// generated modules would shadow
+const RESERVED_MODULES: &[&str] = &["alloc", "core", "proc_macro", "std", "test", "tests"];
+
+/// Converts the name of a block into the name of its generated module, which starts with the
+/// given `prefix` and is suffixed with an underscore if it would shadow a crate (e.g. `std_`)
+pub(crate) fn module_name(name: &str, prefix: &str) -> String {
+    let mut ident = format!("{}{}", prefix, ident_name(name));
+    if RESERVED_MODULES.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+
+    ident
+}
+
+/// Converts the name of a block into a valid identifier in snake case, where:
+/// - Apostrophes are removed (e.g. `isn't` becomes `isnt`), and other punctuation separates words
+/// - Non-ASCII text is transliterated (e.g. `café` becomes `cafe`), or escaped by its code point
+///   if it can't be (e.g. `🦀` becomes `u1f980`)
+/// - Names starting with a digit are prefixed with an underscore (e.g. `_2_2_is_4`)
+/// - Names without any letters or digits become `unnamed`
+/// - Keywords are suffixed with an underscore (e.g. `match_`)
+pub(crate) fn ident_name(name: &str) -> String {
+    let ascii = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                return c.to_string();
+            } else if c == '\'' {
+                return String::new();
+            } else if c.is_ascii() {
+                return String::from(" ");
+            }
+            let latin = latinise(&c.to_string());
+            if latin.chars().any(|c| c.is_ascii_alphanumeric()) {
+                latin
+            } else {
+                format!(" u{:x} ", c as u32)
+            }
+        })
+        .collect::<String>();
+
+    let mut ident = snake_case(&ascii);
+    if ident.is_empty() {
+        ident.push_str(UNNAMED);
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+
+    ident
+}
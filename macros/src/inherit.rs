@@ -0,0 +1,193 @@
+//! Defines the inheritance behavior of `Describe` and `Test` block properties
+
+use crate::block::{BasicBlock, BlockProps, Describe, DescribeProps, Test};
+
+/// The trait and respective function for inheriting the parent `Describe` block's properties
+pub(crate) trait Inherit {
+    fn inherit(&mut self, parent_props: &DescribeProps);
+}
+
+impl Inherit for Describe {
+    fn inherit(&mut self, parent_props: &DescribeProps) {
+        // Inherit the `BlockProps` shared with `Test` blocks
+        self.properties.block_props.inherit(parent_props);
+
+        // Inherit `before_all`/`after_all` blocks from parent, placing this block's own hooks
+        // after its ancestors' hooks
+        let mut once_hooks = parent_props.once_hooks.clone();
+        for mut self_hooks in self.properties.once_hooks.drain(..) {
+            self_hooks.index = once_hooks.len();
+            self_hooks.is_async = self.properties.block_props.is_async;
+            once_hooks.push(self_hooks);
+        }
+        self.properties.once_hooks = once_hooks;
+
+        // Inherit the `let` declarations that this block doesn't override from parent
+        let lets = parent_props
+            .lets
+            .iter()
+            .filter(|parent_let| {
+                !self
+                    .properties
+                    .lets
+                    .iter()
+                    .any(|self_let| self_let.name == parent_let.name)
+            })
+            .chain(self.properties.lets.iter())
+            .cloned()
+            .collect();
+        self.properties.lets = lets;
+
+        // Inherit `around` blocks from parent, which wrap this block's own `around` block
+        let mut around = parent_props.around.clone();
+        for mut self_around in self.properties.around.drain(..) {
+            self_around.is_async = self.properties.block_props.is_async;
+            around.push(self_around);
+        }
+        self.properties.around = around;
+
+        // Inherit the fixtures that this block doesn't override from parent
+        let fixtures = parent_props
+            .fixtures
+            .iter()
+            .filter(|parent_fixture| {
+                !self
+                    .properties
+                    .fixtures
+                    .iter()
+                    .any(|self_fixture| self_fixture.name == parent_fixture.name)
+            })
+            .chain(self.properties.fixtures.iter())
+            .cloned()
+            .collect();
+        self.properties.fixtures = fixtures;
+
+        // If self doesn't have a `subject` block, use its parent's
+        if self.properties.subject.is_none() {
+            self.properties.subject = parent_props.subject.clone();
+        }
+
+        // Inherit `before` code sequences from parent
+        if let Some(ref parent_props_before) = &parent_props.before {
+            // Prepend parent_props's `before` code sequence
+            let before = if let Some(ref self_before) = &self.properties.before {
+                parent_props_before
+                    .0
+                    .iter()
+                    .chain(self_before.0.iter())
+                    .cloned()
+                    .collect()
+            } else {
+                parent_props_before.0.clone()
+            };
+
+            // Errors about the joined code sequence point at this block's `before` block, if any
+            let span = self
+                .properties
+                .before
+                .as_ref()
+                .map_or(parent_props_before.1, |self_before| self_before.1);
+            self.properties.before = Some(BasicBlock(before, span));
+        }
+
+        // Inherit `after` code sequences from parent
+        if let Some(ref parent_props_after) = &parent_props.after {
+            // Append parent_props's `after` code sequence
+            if let Some(ref mut self_after) = &mut self.properties.after {
+                self_after
+                    .content
+                    .0
+                    .extend(parent_props_after.content.0.clone());
+                self_after.uses_outcome |= parent_props_after.uses_outcome;
+            } else {
+                self.properties.after = Some(parent_props_after.clone());
+            }
+        }
+    }
+}
+
+impl Inherit for Test {
+    fn inherit(&mut self, parent_props: &DescribeProps) {
+        // A limit declared on a test without a key is shared with its siblings
+        self.properties
+            .scope_concurrency(&parent_props.block_props.full_description());
+
+        // Inherit the `BlockProps` shared with `Describe` blocks
+        self.properties.inherit(parent_props);
+
+        // Inherit `before_all`/`after_all` blocks from parent
+        self.once_hooks = parent_props.once_hooks.clone();
+
+        // Inherit `let` declarations from parent
+        self.lets = parent_props.lets.clone();
+
+        // Inherit the `subject` block from parent
+        self.subject = parent_props.subject.clone();
+
+        // Inherit `around` blocks from parent
+        self.around = parent_props.around.clone();
+
+        // Inherit fixtures from parent
+        self.fixtures = parent_props.fixtures.clone();
+
+        // Prepend `before` code sequence from parent
+        if let Some(ref parent_props_before) = &parent_props.before {
+            self.before = parent_props_before
+                .0
+                .iter()
+                .chain(self.before.iter())
+                .cloned()
+                .collect();
+        }
+
+        // Inherit `after` code sequence from parent
+        self.after = parent_props.after.clone();
+    }
+}
+
+impl Inherit for BlockProps {
+    fn inherit(&mut self, parent_props: &DescribeProps) {
+        // Append attributes from parent, keeping this block's own `#[ignore]` attribute if any
+        let is_ignored = self
+            .attributes
+            .iter()
+            .any(|attribute| attribute.path.is_ident("ignore"));
+        self.attributes.extend(
+            parent_props
+                .block_props
+                .attributes
+                .iter()
+                .filter(|attribute| !is_ignored || !attribute.path.is_ident("ignore"))
+                .cloned(),
+        );
+
+        // If parent is async, so is self
+        if !self.is_async && parent_props.block_props.is_async {
+            self.is_async = true;
+        }
+
+        // Follow the descriptions of the parent and its ancestors
+        self.path = parent_props.block_props.path.clone();
+        self.path.push(parent_props.block_props.description.clone());
+
+        // If self doesn't have a time limit, use its parent's
+        if self.timeout.is_none() {
+            self.timeout = parent_props.block_props.timeout.clone()
+        }
+
+        // If self doesn't have a number of retries, use its parent's
+        if self.retries.is_none() {
+            self.retries = parent_props.block_props.retries
+        }
+
+        // If self doesn't have a limit on how many tests may run at once, use its parent's
+        if self.concurrency.is_none() {
+            self.concurrency = parent_props.block_props.concurrency.clone()
+        }
+
+        // If self doesn't have a return type, use its parent's
+        if self.return_type.is_none() {
+            self.return_type = parent_props.block_props.return_type.clone()
+        }
+    }
+}
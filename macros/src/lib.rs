@@ -0,0 +1,38 @@
+//! The procedural macro of the `demonstrate` testing framework, which should be used through the
+//! `demonstrate` crate that provides the items its tests rely on at runtime
+
+extern crate proc_macro;
+
+use crate::block::Root;
+use crate::generate::Generate;
+
+mod block;
+mod expand;
+mod fixtures;
+mod focus;
+mod generate;
+mod ident;
+mod inherit;
+mod names;
+
+#[proc_macro]
+pub fn demonstrate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+
+    let output = match parse(input) {
+        Ok(mut root) => root.generate(None),
+        Err(error) => error.to_compile_error(),
+    };
+
+    proc_macro::TokenStream::from(output)
+}
+
+/// Parses the root blocks and applies the passes that precede their generation
+fn parse(input: proc_macro2::TokenStream) -> syn::Result<Root> {
+    let mut root = syn::parse2::<Root>(input)?;
+    root.expand()?;
+    root.check_names()?;
+    root.check_fixtures()?;
+    root.focus()?;
+    Ok(root)
+}
@@ -0,0 +1,86 @@
+//! Defines the detection of sibling blocks whose names generate the same identifier, which happens
+//! before the generation of blocks
+
+use crate::block::*;
+use syn::parse::{Error, Result};
+
+/// The names generated for sibling blocks of one kind, along with the blocks they were generated
+/// for
+#[derive(Default)]
+struct Siblings<'a> {
+    generated: Vec<(String, &'a BlockProps)>,
+}
+
+impl<'a> Siblings<'a> {
+    /// Records the names generated for a block, reporting those that a previous sibling already
+    /// generated
+    fn insert(&mut self, names: Vec<String>, props: &'a BlockProps, errors: &mut Errors) {
+        for name in names {
+            if let Some((_, other)) = self.generated.iter().find(|(other, _)| *other == name) {
+                errors.push(collision(&name, props, other));
+            } else {
+                self.generated.push((name, props));
+            }
+        }
+    }
+}
+
+impl Root {
+    /// Checks that no sibling blocks generate the same module or function name
+    pub(crate) fn check_names(&self) -> Result<()> {
+        let mut errors = Errors::default();
+        let mut modules = Siblings::default();
+        for describe in &self.blocks {
+            modules.insert(
+                vec![describe.module_name()],
+                &describe.properties.block_props,
+                &mut errors,
+            );
+            describe.check_names(&mut errors);
+        }
+
+        errors.finish()
+    }
+}
+
+impl Describe {
+    /// Checks the names generated for the blocks within this block and its descendants
+    fn check_names(&self, errors: &mut Errors) {
+        let mut modules = Siblings::default();
+        let mut functions = Siblings::default();
+        for block in &self.blocks {
+            match block {
+                Block::Describe(describe) => {
+                    modules.insert(
+                        vec![describe.module_name()],
+                        &describe.properties.block_props,
+                        errors,
+                    );
+                    describe.check_names(errors);
+                }
+                Block::Test(test) => functions.insert(test.fn_names(), &test.properties, errors),
+                Block::ItBehavesLike(_) => {}
+            }
+        }
+    }
+}
+
+/// Creates an error pointing at the names of both colliding blocks
+fn collision(name: &str, props: &BlockProps, other: &BlockProps) -> Error {
+    let mut error = Error::new(
+        props.name.span(),
+        format!(
+            "The name \"{}\" generates `{}`, which the sibling block named \"{}\" already \
+             generates",
+            props.description, name, other.description
+        ),
+    );
+    error.combine(Error::new(
+        other.name.span(),
+        format!(
+            "`{}` is first generated for the name \"{}\"",
+            name, other.description
+        ),
+    ));
+    error
+}
@@ -192,24 +192,894 @@
 //!     }
 //! }
 //! ```
-//! **Note:** If a `describe`/`context` block has a return type with an `after` block containing a
-//! success result type being returned, keep in mind that a compile error will occur if a descendant test
-//! has different return type than the one appearing in that `after` block.
-
-extern crate proc_macro;
-
-use crate::block::Root;
-use crate::generate::Generate;
+//!
+//! <hr />
+//!
+//! `async` tests run on the async runtime whose test attribute they're given (e.g.
+//! `#[async_std::test]`), which is recognized by its name ending in `test`. Without one, the test
+//! is driven by a small executor that's bundled with this crate, which blocks the test's thread
+//! until the test's future is ready.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     async describe "asynchronous" {
+//!         it "awaits" {
+//!             assert_eq!(async { 4 }.await, 4)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod asynchronous {
+//!     #[test]
+//!     fn awaits() {
+//!         async fn __test() {
+//!             assert_eq!(async { 4 }.await, 4)
+//!         }
+//!         ::demonstrate::__private::block_on(__test())
+//!     }
+//! }
+//! ```
+//! **Note:** The bundled executor doesn't drive a runtime's I/O or timers, so tests relying on
+//! those (e.g. of `tokio`) still need the runtime's test attribute.
+//!
+//! <hr />
+//!
+//! A `timeout(<duration>)` following the name of a block fails its tests once they run for longer
+//! than the duration, which is either a number of seconds (`2s`), a number of milliseconds
+//! (`500ms`), or a `std::time::Duration` expression. Like return types, timeouts are inherited by
+//! blocks without one already defined.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "slow" timeout(2s) {
+//!         it "finishes" {
+//!             std::thread::sleep(std::time::Duration::from_millis(10))
+//!         }
+//!
+//!         it "finishes sooner" timeout(500ms) {}
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod slow {
+//!     #[test]
+//!     fn finishes() {
+//!         // Runs the test on a separate thread, panicking with "`slow > finishes` exceeded its
+//!         // timeout of 2s" if it doesn't finish in time
+//!         ::demonstrate::__private::timeout(std::time::Duration::from_secs_f64(2.0), "slow > finishes", move || -> () {
+//!             std::thread::sleep(std::time::Duration::from_millis(10))
+//!         })
+//!     }
+//!
+//!     #[test]
+//!     fn finishes_sooner() {
+//!         ::demonstrate::__private::timeout(
+//!             std::time::Duration::from_secs_f64(500.0 / 1000.0),
+//!             "slow > finishes sooner",
+//!             move || -> () {},
+//!         )
+//!     }
+//! }
+//! ```
+//! **Note:** As the tests run on a separate thread, which is left running once they exceed their
+//! timeout, they can't borrow anything from the test's thread. Only the `Debug` representation of
+//! a returned error is sent back, so the return type needn't be `Send`. `async` tests are instead
+//! polled until their deadline, which can't interrupt a test that blocks its thread.
+//!
+//! <hr />
+//!
+//! A `retry(<n>)` following the name of a block makes up to `n` more attempts at its tests when
+//! they fail, running their `before` and `after` blocks again for each attempt. A test passes once
+//! any attempt does, and the failure of each previous attempt is written to the standard error so
+//! that flaky tests stay visible. Retries are inherited like timeouts, which apply to each attempt.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "flaky" retry(2) {
+//!         it "connects" {
+//!             assert!(true)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod flaky {
+//!     #[test]
+//!     fn connects() {
+//!         // Writes "`flaky > connects` failed attempt 1 of 3: <message>" for each failed attempt
+//!         ::demonstrate::__private::retry(2, "flaky > connects", || {
+//!             assert!(true)
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! <hr />
+//!
+//! A `serial` following the name of a block runs its tests one at a time, and a
+//! `parallel(max = <n>)` runs at most `n` of them at once. When given a key, as in
+//! `serial("port")` or `parallel("database", max = 2)`, the limit is shared by every test with the
+//! same key instead. Without one, it's shared by the tests within the block, or with the sibling
+//! tests declaring a limit if it's given to a test. When tests sharing a key declare different
+//! limits, the smallest one applies to all of them. Limits are inherited like timeouts.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "server" serial("port") {
+//!         it "binds the port" {
+//!             assert!(true)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod server {
+//!     #[test]
+//!     fn binds_the_port() {
+//!         // Waits until no other test with the "port" key is running
+//!         let __permit = ::demonstrate::__private::acquire("resource port", 1u32);
+//!         assert!(true)
+//!     }
+//! }
+//! ```
+//! **Note:** Keys are shared across every `demonstrate!` invocation of the test binary.
+//!
+//! <hr />
+//!
+//! `expect_snapshot!(<value>)` asserts that the `Debug` representation of a value matches the
+//! snapshot recorded for the test, or its `Display` representation when given `%<value>`. The
+//! first run of a test records its snapshots into the `snapshots` directory of the crate, at the
+//! path of the test's module and named after its function (e.g.
+//! `snapshots/my_crate/point/prints.snap`). Later runs fail with a line diff when a value doesn't
+//! match its snapshot, unless the `DEMONSTRATE_UPDATE=1` environment variable is set, which
+//! records the snapshots again.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "point" {
+//!         use demonstrate::expect_snapshot;
+//!
+//!         it "prints" {
+//!             expect_snapshot!((1, 2));
+//!             expect_snapshot!(%"(1, 2)")
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod point {
+//!     use demonstrate::expect_snapshot;
+//!
+//!     #[test]
+//!     fn prints() {
+//!         static __SNAPSHOTS: ::demonstrate::__private::Snapshots = ::demonstrate::__private::Snapshots::new(
+//!             env!("CARGO_MANIFEST_DIR"),
+//!             module_path!(),
+//!             "prints",
+//!             "point > prints",
+//!         );
+//!         __SNAPSHOTS.reset();
+//!         // Compares against `snapshots/my_crate/point/prints.snap`
+//!         __SNAPSHOTS.assert(&format!("{:#?}", (1, 2)));
+//!         // Compares against `snapshots/my_crate/point/prints-2.snap`
+//!         __SNAPSHOTS.assert(&format!("{}", "(1, 2)"))
+//!     }
+//! }
+//! ```
+//! **Note:** Snapshots that haven't been recorded fail when the `CI` environment variable is set to
+//! anything but an empty string, `false` or `0`, so they must be recorded and committed beforehand.
+//!
+//! <hr />
+//!
+//! Expectations can be written as `expect(<value>).to(<matcher>)`, or `.not_to(<matcher>)` to
+//! negate them. Their failure messages describe the test along with the value and what was
+//! expected of it, e.g. "`math > adds` expected 4 to equal 5". The matchers are `eq`,
+//! `be_greater_than`, `be_less_than`, `be_close_to`, `contain`, `be_empty`, `be_ok`, `be_err`,
+//! `be_some`, `be_none` and `satisfy`, which can be combined with `.and(<matcher>)`,
+//! `.or(<matcher>)` and `not(<matcher>)`. Custom matchers implement the `Matcher` trait. These are
+//! imported from the `matchers` module, e.g. with `use demonstrate::matchers::*;`.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "math" {
+//!         use demonstrate::matchers::*;
+//!
+//!         it "adds" {
+//!             expect(2 + 3).to(eq(5));
+//!             expect(vec![1, 2, 3]).to(contain(&3).and(not(be_empty())));
+//!             expect("one".parse::<u8>()).to(be_err());
+//!             expect(0.1 + 0.2).to(be_close_to(0.3, 1e-9))
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod math {
+//!     use demonstrate::matchers::*;
+//!
+//!     #[test]
+//!     fn adds() {
+//!         fn expect<T: std::fmt::Debug>(actual: T) -> ::demonstrate::__private::Expectation<T> {
+//!             ::demonstrate::__private::Expectation::new(actual, "math > adds")
+//!         }
+//!
+//!         expect(2 + 3).to(eq(5));
+//!         expect(vec![1, 2, 3]).to(contain(&3).and(not(be_empty())));
+//!         expect("one".parse::<u8>()).to(be_err());
+//!         expect(0.1 + 0.2).to(be_close_to(0.3, 1e-9))
+//!     }
+//! }
+//! ```
+//!
+//! <hr />
+//!
+//! `after` blocks run on every exit path of a test, whether it finishes, returns early, fails
+//! through `?` or panics. The result of the test is captured and returned once the `after` block
+//! has run.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "files" -> std::io::Result<()> {
+//!         before {
+//!             let path = std::env::temp_dir().join("demonstrate");
+//!         }
+//!
+//!         after {
+//!             let _ = std::fs::remove_file(&path);
+//!         }
+//!
+//!         it "writes" {
+//!             std::fs::write(&path, "contents")?;
+//!             Ok(())
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod files {
+//!     #[test]
+//!     fn writes() -> std::io::Result<()> {
+//!         let path = std::env::temp_dir().join("demonstrate");
+//!         let __result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(
+//!             || -> std::io::Result<()> {
+//!                 std::fs::write(&path, "contents")?;
+//!                 Ok(())
+//!             },
+//!         ));
+//!
+//!         {
+//!             let _ = std::fs::remove_file(&path);
+//!         }
+//!
+//!         match __result {
+//!             Ok(result) => result,
+//!             Err(panic) => std::panic::resume_unwind(panic),
+//!         }
+//!     }
+//! }
+//! ```
+//! **Note:** Within `async` tests, panics are caught while polling the test's contents instead.
+//!
+//! <hr />
+//!
+//! `after` blocks can bind the outcome of the test, which tells whether the test passed, returned an
+//! `Err` or panicked, along with the error or panic message.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "diagnostics" {
+//!         before {
+//!             let state = vec![1, 2, 3];
+//!         }
+//!
+//!         after |outcome| {
+//!             if outcome.failed() {
+//!                 eprintln!("{:?} with state {:?}", outcome.message(), state);
+//!             }
+//!         }
+//!
+//!         it "checks the state" {
+//!             assert_eq!(state.len(), 3)
+//!         }
+//!     }
+//! }
+//! ```
+//! The outcome is a reference to the following `demonstrate::Outcome` enum, which provides the
+//! `passed()`, `failed()`, `panicked()` and `message()` methods:
+//! ```
+//! pub enum Outcome {
+//!     Passed,
+//!     Errored(String),
+//!     Panicked(String),
+//! }
+//! ```
+//!
+//! <hr />
+//!
+//! `it`/`test` blocks can be parameterized with a table of cases, generating one test per case with
+//! the case's value bound to the given pattern.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "math" {
+//!         it "adds" for (a, b, sum) in [(1, 2, 3), (2, 2, 4)] {
+//!             assert_eq!(a + b, sum)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod math {
+//!     #[test]
+//!     fn adds_with_1_2_3() {
+//!         let (a, b, sum) = (1, 2, 3);
+//!         assert_eq!(a + b, sum)
+//!     }
+//!
+//!     #[test]
+//!     fn adds_with_2_2_4() {
+//!         let (a, b, sum) = (2, 2, 4);
+//!         assert_eq!(a + b, sum)
+//!     }
+//! }
+//! ```
+//! **Note:** If the values of the cases don't produce distinct names, the tests are numbered
+//! instead (e.g. `adds_case_1`, `adds_case_2`)
+//!
+//! <hr />
+//!
+//! `it`/`test` blocks can instead be property tests with `forall (<inputs>)`, which runs the test
+//! for many pseudo-random inputs. Each input is either generated from its type, as in `v: Vec<u8>`,
+//! or from a range, as in `n in 0..100u32`. When the test fails, its inputs are shrunk towards the
+//! smallest ones it still fails for, which are reported along with the seed of the inputs.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "vec" {
+//!         it "reverses twice" forall (v: Vec<u8>, n in 0..100u32) {
+//!             let mut reversed = v.clone();
+//!             reversed.reverse();
+//!             reversed.reverse();
+//!             assert_eq!(reversed, v)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod vec {
+//!     #[test]
+//!     fn reverses_twice() {
+//!         // Panics with e.g. "`vec > reverses twice` failed for v = [0], n = 0 (found in case 3
+//!         // with the seed 42 and shrunk 2 times, rerun with DEMONSTRATE_SEED=42 to reproduce)"
+//!         ::demonstrate::__private::forall(
+//!             "vec > reverses twice",
+//!             &["v", "n"],
+//!             (::demonstrate::__private::any::<Vec<u8>>(), 0..100u32),
+//!             |(v, n)| {
+//!                 let mut reversed = v.clone();
+//!                 reversed.reverse();
+//!                 reversed.reverse();
+//!                 assert_eq!(reversed, v)
+//!             },
+//!         )
+//!     }
+//! }
+//! ```
+//! **Note:** Tests run for 100 inputs unless the `DEMONSTRATE_CASES` environment variable is set.
+//! Inputs can be generated from integer ranges, or from the integer types, `bool`, `char`,
+//! `String`, and `Vec`s, `Option`s and tuples of these types.
+//!
+//! <hr />
+//!
+//! `let` declarations within `describe`/`context` blocks are only evaluated by the tests that use
+//! them, either directly or through another `let` declaration. Nested `describe`/`context` blocks can
+//! override them, which also affects the declarations depending on them. They are evaluated after
+//! the `before` code sequence, unless it uses them.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "rectangle" {
+//!         let width = 2;
+//!         let height = 3;
+//!         let area = width * height;
+//!
+//!         it "has an area" {
+//!             assert_eq!(area, 6)
+//!         }
+//!
+//!         context "tall" {
+//!             let height = 10;
+//!
+//!             it "has a larger area" {
+//!                 assert_eq!(area, 20)
+//!             }
+//!
+//!             it "has a height" {
+//!                 assert_eq!(height, 10)
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod rectangle {
+//!     #[test]
+//!     fn has_an_area() {
+//!         let width = 2;
+//!         let height = 3;
+//!         let area = width * height;
+//!         assert_eq!(area, 6)
+//!     }
+//!
+//!     mod tall {
+//!         use super::*;
+//!
+//!         #[test]
+//!         fn has_a_larger_area() {
+//!             let width = 2;
+//!             let height = 10;
+//!             let area = width * height;
+//!             assert_eq!(area, 20)
+//!         }
+//!
+//!         #[test]
+//!         fn has_a_height() {
+//!             let height = 10;
+//!             assert_eq!(height, 10)
+//!         }
+//!     }
+//! }
+//! ```
+//! **Note:** `let` declarations are evaluated in each test that uses them, after its `before` code
+//! sequence unless that uses them too, in which case they're evaluated before it. Uses within the
+//! format strings of macros, such as `format!("{area}")`, count as well.
+//!
+//! <hr />
+//!
+//! Tests can request fixtures as parameters, e.g. `it "queries" |db: &Db| {}`, which are resolved
+//! from the `fixture name: Type = value;` declarations of the closest ancestoral
+//! `describe`/`context` block declaring them. Each test builds only the fixtures it requests, along
+//! with the fixtures their values refer to, and can borrow them (`&Type` or `&mut Type`) or take
+//! them (`Type`). Parameters without a type borrow their fixture. A return type can follow the
+//! parameters, e.g. `|db: &Db| -> Result<(), String>`.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "users" {
+//!         fixture names: Vec<String> = vec![String::from("alice")];
+//!         fixture count: usize = names.len();
+//!         fixture unused: String = unreachable!();
+//!
+//!         it "counts" |count: &usize| {
+//!             assert_eq!(*count, 1)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod users {
+//!     #[test]
+//!     fn counts() {
+//!         let mut names: Vec<String> = vec![String::from("alice")];
+//!         let mut count: usize = names.len();
+//!         let count: &usize = &count;
+//!         assert_eq!(*count, 1)
+//!     }
+//! }
+//! ```
+//! **Note:** Fixtures are built after the `let` declarations that they refer to, and before the
+//! `before` block.
+//!
+//! <hr />
+//!
+//! A `subject {}` block declares the value that a `describe`/`context` block is about, which its
+//! tests can refer to as `subject` and nested blocks can override. `is_expected` expects something
+//! of the subject, so tests can be written as one-liners without a name: either
+//! `it { is_expected.to(<matcher>) }`, or `it => <value>` to expect the subject to equal the value.
+//! Tests without a name are described by their contents, and named tests can use `=>` as well.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "numbers" {
+//!         use demonstrate::matchers::*;
+//!
+//!         subject { vec![1, 2, 3] }
+//!
+//!         it { is_expected.to(contain(&2)) }
+//!
+//!         context "when empty" {
+//!             subject { Vec::<u8>::new() }
+//!
+//!             it "has nothing" => vec![]
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```ignore
+//! #[cfg(test)]
+//! mod numbers {
+//!     use demonstrate::matchers::*;
+//!
+//!     #[test]
+//!     fn is_expected_to_contain_2() {
+//!         let subject = { vec![1, 2, 3] };
+//!         let is_expected = expect(subject);
+//!         is_expected.to(contain(&2));
+//!     }
+//!
+//!     mod when_empty {
+//!         use super::*;
+//!
+//!         #[test]
+//!         fn has_nothing() {
+//!             let subject = { Vec::<u8>::new() };
+//!             let is_expected = expect(subject);
+//!             is_expected.to(::demonstrate::matchers::eq(vec![]));
+//!         }
+//!     }
+//! }
+//! ```
+//! **Note:** `is_expected` takes the subject, which is evaluated like a `let` declaration at the
+//! start of each test that uses it.
+//!
+//! <hr />
+//!
+//! `shared` blocks define a group of tests and nested `describe`/`context` blocks that can be
+//! included by `it_behaves_like` statements, either at the root of the `demonstrate!` macro or
+//! within a `describe`/`context` block and its descendants. The included group becomes a nested
+//! `describe`/`context` block of the same name, whose parameters are declared as `let` bindings of the
+//! given arguments. A group included more than once by the same block is numbered by the order of
+//! its inclusions (e.g. `a collection 1` and `a collection 2`).
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     shared "a collection" (empty: Vec<u8>) {
+//!         it "is empty" {
+//!             assert!(empty.is_empty())
+//!         }
+//!     }
+//!
+//!     describe "vec" {
+//!         it_behaves_like "a collection"(Vec::new());
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod vec {
+//!     mod a_collection {
+//!         use super::*;
+//!
+//!         #[test]
+//!         fn is_empty() {
+//!             let empty: Vec<u8> = Vec::new();
+//!             assert!(empty.is_empty())
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! <hr />
+//!
+//! `describe`/`context` blocks can be instantiated for several types with `for T in [<types>]`, or
+//! several constants with `for const N: <type> in [<values>]`. Each instance is generated into a
+//! separate module named after its type or value, in which `T` is declared as a type alias or `N`
+//! as a constant.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "stack" for T in [Vec<u8>, std::collections::VecDeque<u8>] {
+//!         it "starts empty" {
+//!             assert!(T::new().is_empty())
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod stack {
+//!     use super::*;
+//!
+//!     mod vec_u8 {
+//!         type T = Vec<u8>;
+//!
+//!         #[test]
+//!         fn starts_empty() {
+//!             assert!(T::new().is_empty())
+//!         }
+//!     }
+//!
+//!     mod std_collections_vec_deque_u8 {
+//!         type T = std::collections::VecDeque<u8>;
+//!
+//!         #[test]
+//!         fn starts_empty() {
+//!             assert!(T::new().is_empty())
+//!         }
+//!     }
+//! }
+//! ```
+//! **Note:** If the types or values don't produce distinct names, the modules are numbered instead
+//! (e.g. `t_1`, `t_2`)
+//!
+//! <hr />
+//!
+//! `xit`/`xtest` and `xdescribe`/`xcontext` blocks are ignored along with their descendant tests, as
+//! are blocks preceded by `skip "<reason>"`. Tests without contents are pending, and ignored until
+//! their contents are written.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "outline" {
+//!         xit "is skipped" {
+//!             assert!(false)
+//!         }
+//!
+//!         skip "the server is down" it "is skipped with a reason" {
+//!             assert!(false)
+//!         }
+//!
+//!         it "is pending";
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod outline {
+//!     #[test]
+//!     #[ignore]
+//!     fn is_skipped() {
+//!         assert!(false)
+//!     }
+//!
+//!     #[test]
+//!     #[ignore = "the server is down"]
+//!     fn is_skipped_with_a_reason() {
+//!         assert!(false)
+//!     }
+//!
+//!     #[test]
+//!     #[ignore = "pending"]
+//!     fn is_pending() {}
+//! }
+//! ```
+//!
+//! <hr />
+//!
+//! `fit`/`ftest` and `fdescribe`/`fcontext` blocks are focused: while any block within a
+//! `demonstrate!` invocation is focused, every test outside of the focused blocks is ignored. As
+//! focusing is only meant for local development, the macro fails to compile when the `CI`
+//! environment variable is set to anything but an empty string, `false` or `0`. Cargo rebuilds the
+//! tests of a focused invocation once the variable changes.
+//! ```ignore
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     describe "outline" {
+//!         fit "is focused" {
+//!             assert!(true)
+//!         }
+//!
+//!         it "is not focused" {
+//!             assert!(true)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod outline {
+//!     #[test]
+//!     fn is_focused() {
+//!         assert!(true)
+//!     }
+//!
+//!     #[test]
+//!     #[ignore = "not focused"]
+//!     fn is_not_focused() {
+//!         assert!(true)
+//!     }
+//! }
+//! ```
+//!
+//! <hr />
+//!
+//! `before_all` and `after_all` blocks run once for all tests within the `describe`/`context` block
+//! they are contained in and its nested `describe`/`context` blocks. Each `let` binding within a
+//! `before_all` block needs a type, as its value is stored in a lazily-initialized `static` and
+//! borrowed by each test. The `after_all` block runs after the last of these tests has finished.
+//! ```
+//! # use demonstrate::demonstrate;
+//! # struct Server;
+//! # impl Server {
+//! #     fn start() -> Self { Server }
+//! #     fn port(&self) -> u16 { 8080 }
+//! #     fn stop(&self) {}
+//! # }
+//! demonstrate! {
+//!     describe "server" {
+//!         use super::*;
+//!
+//!         before_all {
+//!             let server: Server = Server::start();
+//!         }
+//!
+//!         after_all {
+//!             server.stop();
+//!         }
+//!
+//!         it "listens" {
+//!             assert_eq!(server.port(), 8080)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! # struct Server;
+//! # impl Server {
+//! #     fn start() -> Self { Server }
+//! #     fn port(&self) -> u16 { 8080 }
+//! #     fn stop(&self) {}
+//! # }
+//! #[cfg(test)]
+//! mod server {
+//!     use super::*;
+//!
+//!     static __BEFORE_ALL_0: std::sync::OnceLock<(Server,)> = std::sync::OnceLock::new();
+//!
+//!     fn __before_all_0() -> &'static (Server,) {
+//!         __BEFORE_ALL_0.get_or_init(|| {
+//!             let server: Server = Server::start();
+//!             (server,)
+//!         })
+//!     }
+//!
+//!     // Counts the finished tests, calling `__after_all_0` after the last one that runs
+//!     struct __AfterAll0;
+//!     # fn __after_all_0() {}
+//!
+//!     #[test]
+//!     fn listens() {
+//!         let __after_all = (__AfterAll0,);
+//!         let (ref server,) = *__before_all_0();
+//!         assert_eq!(server.port(), 8080)
+//!     }
+//! }
+//! ```
+//! **Note:** The values of a `before_all` block must be `Send + Sync`. Within `async`
+//! `describe`/`context` blocks, `before_all` blocks may `.await`. The `after_all` block only waits
+//! for the tests that run, according to the filters, `--skip`, `--exact`, `--ignored` and
+//! `--include-ignored` arguments of the test binary.
+//!
+//! <hr />
+//!
+//! `around |run| {}` blocks wrap each test within the `describe`/`context` block they are
+//! contained in and its nested `describe`/`context` blocks, including the `before` and `after`
+//! blocks of the test. The test runs when `run()` is called, so the `around` block can run it
+//! within a scope, such as while holding a lock. Nested `around` blocks run within the `around`
+//! blocks of their ancestors.
+//! ```
+//! # use demonstrate::demonstrate;
+//! # static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+//! demonstrate! {
+//!     describe "database" {
+//!         use super::*;
+//!
+//!         around |run| {
+//!             let _guard = LOCK.lock().unwrap();
+//!             run();
+//!         }
+//!
+//!         it "migrates" {
+//!             assert!(true)
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! # static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+//! #[cfg(test)]
+//! mod database {
+//!     use super::*;
+//!
+//!     #[test]
+//!     fn migrates() {
+//!         let __output = std::cell::Cell::new(None);
+//!         {
+//!             let __result = &__output;
+//!             let run = move || __result.set(Some((move || {
+//!                 assert!(true)
+//!             })()));
+//!             {
+//!                 let _guard = LOCK.lock().unwrap();
+//!                 run();
+//!             }
+//!         }
+//!         // Panics if the `around` block didn't call `run()`
+//!         ::demonstrate::__private::ran_around(__output.into_inner(), "database > migrates")
+//!     }
+//! }
+//! ```
+//! **Note:** Within `async` `describe`/`context` blocks, `run()` returns a future to `.await`.
+//! Otherwise, `async` tests are blocked on by `run()`.
+//!
+//! <hr />
+//!
+//! Names are converted into snake case identifiers, where modules that would shadow a crate that's
+//! always in scope (such as `std` or `core`) are suffixed with an underscore. To keep the modules
+//! apart from any other crate or module, a `#![module_prefix = "<prefix>"]` option can be given at
+//! the start of the macro, which is prepended to the name of every `describe`/`context` module.
+//! ```
+//! # use demonstrate::demonstrate;
+//! demonstrate! {
+//!     #![module_prefix = "spec_"]
+//!
+//!     describe "std" {
+//!         it "uses the real crate" {
+//!             assert!(std::collections::HashSet::<u8>::new().is_empty())
+//!         }
+//!     }
+//! }
+//! ```
+//! This is generated into:
+//! ```
+//! #[cfg(test)]
+//! mod spec_std {
+//!     #[test]
+//!     fn uses_the_real_crate() {
+//!         assert!(std::collections::HashSet::<u8>::new().is_empty())
+//!     }
+//! }
+//! ```
 
-mod block;
-mod generate;
-mod inherit;
+#![allow(clippy::test_attr_in_doctest)]
 
-#[proc_macro]
-pub fn demonstrate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = proc_macro2::TokenStream::from(input);
+pub use demonstrate_macros::demonstrate;
+pub use outcome::Outcome;
 
-    let mut root = syn::parse2::<Root>(input).unwrap();
+pub mod matchers;
+mod once;
+mod outcome;
+mod property;
+mod runtime;
+mod snapshot;
 
-    proc_macro::TokenStream::from(root.generate(None))
+/// The items that generated tests rely on at runtime, which aren't part of the public API
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::matchers::Expectation;
+    pub use crate::once::{selected, OnceAsync};
+    pub use crate::outcome::{Outcome, Report};
+    pub use crate::property::{any, forall, forall_async};
+    pub use crate::runtime::*;
+    pub use crate::snapshot::Snapshots;
 }
@@ -0,0 +1,436 @@
+//! Defines the `expect` function and the matchers checking its expectations, which tests import
+//! with `use demonstrate::matchers::*;`
+
+use std::fmt::Debug;
+
+/// A value whose expectations are checked by matchers, as in `expect(x).to(eq(5))`
+pub struct Expectation<T> {
+    actual: T,
+    description: Option<&'static str>,
+}
+
+/// Expects something of a value, which tests replace with a function that includes
+/// their description in the failure messages
+pub fn expect<T>(actual: T) -> Expectation<T> {
+    Expectation {
+        actual,
+        description: None,
+    }
+}
+
+impl<T: Debug> Expectation<T> {
+    /// Expects something of a value within the test with the given description
+    #[doc(hidden)]
+    pub fn new(actual: T, description: &'static str) -> Self {
+        Expectation {
+            actual,
+            description: Some(description),
+        }
+    }
+
+    /// Fails unless the value matches
+    #[track_caller]
+    pub fn to<M: Matcher<T>>(&self, matcher: M) {
+        if !matcher.matches(&self.actual) {
+            self.fail("to", &matcher);
+        }
+    }
+
+    /// Fails if the value matches
+    #[track_caller]
+    pub fn not_to<M: Matcher<T>>(&self, matcher: M) {
+        if matcher.matches(&self.actual) {
+            self.fail("not to", &matcher);
+        }
+    }
+
+    #[track_caller]
+    fn fail<M: Matcher<T>>(&self, expectation: &str, matcher: &M) -> ! {
+        let description = self
+            .description
+            .map(|description| format!("`{}` ", description))
+            .unwrap_or_default();
+        panic!(
+            "{}expected {:?} {} {}",
+            description,
+            self.actual,
+            expectation,
+            matcher.description()
+        )
+    }
+}
+
+/// Checks whether a value matches an expectation, which is described in failure
+/// messages as what's expected of the value (e.g. "equal 5")
+pub trait Matcher<T: ?Sized> {
+    fn matches(&self, actual: &T) -> bool;
+
+    fn description(&self) -> String;
+}
+
+/// Combines matchers, which is kept apart from `Matcher` so that the type of the
+/// matched value can be inferred from the expectation
+pub trait Compose: Sized {
+    /// Matches values that match both matchers
+    fn and<M>(self, other: M) -> And<Self, M> {
+        And(self, other)
+    }
+
+    /// Matches values that match either matcher
+    fn or<M>(self, other: M) -> Or<Self, M> {
+        Or(self, other)
+    }
+}
+
+macro_rules! compose {
+    ($($matcher:ident $(<$($param:ident),*>)?),*) => {$(
+        impl$(<$($param),*>)? Compose for $matcher$(<$($param),*>)? {}
+    )*};
+}
+
+compose!(
+    And<A, B>,
+    Or<A, B>,
+    Not<M>,
+    Eq<E>,
+    BeGreaterThan<E>,
+    BeLessThan<E>,
+    BeCloseTo<F>,
+    BeEmpty,
+    BeOk,
+    BeErr,
+    BeSome,
+    BeNone,
+    Satisfy<F>
+);
+
+impl<E: ?Sized> Compose for Contain<'_, E> {}
+
+pub struct And<A, B>(A, B);
+
+impl<T: ?Sized, A: Matcher<T>, B: Matcher<T>> Matcher<T> for And<A, B> {
+    fn matches(&self, actual: &T) -> bool {
+        self.0.matches(actual) && self.1.matches(actual)
+    }
+
+    fn description(&self) -> String {
+        format!("{} and {}", self.0.description(), self.1.description())
+    }
+}
+
+pub struct Or<A, B>(A, B);
+
+impl<T: ?Sized, A: Matcher<T>, B: Matcher<T>> Matcher<T> for Or<A, B> {
+    fn matches(&self, actual: &T) -> bool {
+        self.0.matches(actual) || self.1.matches(actual)
+    }
+
+    fn description(&self) -> String {
+        format!("{} or {}", self.0.description(), self.1.description())
+    }
+}
+
+pub struct Not<M>(M);
+
+/// Matches values that don't match the given matcher
+pub fn not<M>(matcher: M) -> Not<M> {
+    Not(matcher)
+}
+
+impl<T: ?Sized, M: Matcher<T>> Matcher<T> for Not<M> {
+    fn matches(&self, actual: &T) -> bool {
+        !self.0.matches(actual)
+    }
+
+    fn description(&self) -> String {
+        format!("not {}", self.0.description())
+    }
+}
+
+pub struct Eq<E>(E);
+
+/// Matches values equal to the expected value
+pub fn eq<E>(expected: E) -> Eq<E> {
+    Eq(expected)
+}
+
+impl<T: PartialEq<E> + ?Sized, E: Debug> Matcher<T> for Eq<E> {
+    fn matches(&self, actual: &T) -> bool {
+        *actual == self.0
+    }
+
+    fn description(&self) -> String {
+        format!("equal {:?}", self.0)
+    }
+}
+
+pub struct BeGreaterThan<E>(E);
+
+/// Matches values greater than the given value
+pub fn be_greater_than<E>(bound: E) -> BeGreaterThan<E> {
+    BeGreaterThan(bound)
+}
+
+impl<T: PartialOrd<E> + ?Sized, E: Debug> Matcher<T> for BeGreaterThan<E> {
+    fn matches(&self, actual: &T) -> bool {
+        *actual > self.0
+    }
+
+    fn description(&self) -> String {
+        format!("be greater than {:?}", self.0)
+    }
+}
+
+pub struct BeLessThan<E>(E);
+
+/// Matches values less than the given value
+pub fn be_less_than<E>(bound: E) -> BeLessThan<E> {
+    BeLessThan(bound)
+}
+
+impl<T: PartialOrd<E> + ?Sized, E: Debug> Matcher<T> for BeLessThan<E> {
+    fn matches(&self, actual: &T) -> bool {
+        *actual < self.0
+    }
+
+    fn description(&self) -> String {
+        format!("be less than {:?}", self.0)
+    }
+}
+
+pub struct BeCloseTo<F>(F, F);
+
+/// Matches floating point numbers within the tolerance of the expected number
+pub fn be_close_to<F>(expected: F, tolerance: F) -> BeCloseTo<F> {
+    BeCloseTo(expected, tolerance)
+}
+
+macro_rules! floats {
+    ($($ty:ty),*) => {$(
+        impl Matcher<$ty> for BeCloseTo<$ty> {
+            fn matches(&self, actual: &$ty) -> bool {
+                (actual - self.0).abs() <= self.1
+            }
+
+            fn description(&self) -> String {
+                format!("be within {:?} of {:?}", self.1, self.0)
+            }
+        }
+    )*};
+}
+
+floats!(f32, f64);
+
+/// The collections that `contain` and `be_empty` match, including strings, which contain
+/// their characters and substrings
+pub trait Collection<E: ?Sized> {
+    fn has(&self, element: &E) -> bool;
+}
+
+/// The collections that `be_empty` matches
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+macro_rules! collections {
+    ($($ty:ty $(where $($bound:tt)*)?),*) => {$(
+        impl<E: PartialEq $(+ $($bound)*)?> Collection<E> for $ty {
+            fn has(&self, element: &E) -> bool {
+                self.iter().any(|candidate| candidate == element)
+            }
+        }
+
+        impl<E> Length for $ty {
+            fn length(&self) -> usize {
+                self.len()
+            }
+        }
+    )*};
+}
+
+collections!(
+    [E],
+    Vec<E>,
+    std::collections::VecDeque<E>,
+    std::collections::LinkedList<E>,
+    std::collections::HashSet<E>,
+    std::collections::BTreeSet<E>
+);
+
+impl<E: PartialEq, const N: usize> Collection<E> for [E; N] {
+    fn has(&self, element: &E) -> bool {
+        self.contains(element)
+    }
+}
+
+impl<E, const N: usize> Length for [E; N] {
+    fn length(&self) -> usize {
+        N
+    }
+}
+
+impl Collection<str> for str {
+    fn has(&self, substring: &str) -> bool {
+        self.contains(substring)
+    }
+}
+
+impl Collection<char> for str {
+    fn has(&self, character: &char) -> bool {
+        self.contains(*character)
+    }
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<E: ?Sized> Collection<E> for String
+where
+    str: Collection<E>,
+{
+    fn has(&self, element: &E) -> bool {
+        self.as_str().has(element)
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<E: ?Sized, C: Collection<E> + ?Sized> Collection<E> for &C {
+    fn has(&self, element: &E) -> bool {
+        (**self).has(element)
+    }
+}
+
+impl<C: Length + ?Sized> Length for &C {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+pub struct Contain<'a, E: ?Sized>(&'a E);
+
+/// Matches collections containing the given element, or strings containing the given
+/// character or substring
+pub fn contain<E: ?Sized>(element: &E) -> Contain<'_, E> {
+    Contain(element)
+}
+
+impl<T: Collection<E> + ?Sized, E: Debug + ?Sized> Matcher<T> for Contain<'_, E> {
+    fn matches(&self, actual: &T) -> bool {
+        actual.has(self.0)
+    }
+
+    fn description(&self) -> String {
+        format!("contain {:?}", self.0)
+    }
+}
+
+pub struct BeEmpty;
+
+/// Matches collections and strings without any elements
+pub fn be_empty() -> BeEmpty {
+    BeEmpty
+}
+
+impl<T: Length + ?Sized> Matcher<T> for BeEmpty {
+    fn matches(&self, actual: &T) -> bool {
+        actual.length() == 0
+    }
+
+    fn description(&self) -> String {
+        String::from("be empty")
+    }
+}
+
+pub struct BeOk;
+
+/// Matches `Ok` results
+pub fn be_ok() -> BeOk {
+    BeOk
+}
+
+impl<V, E> Matcher<Result<V, E>> for BeOk {
+    fn matches(&self, actual: &Result<V, E>) -> bool {
+        actual.is_ok()
+    }
+
+    fn description(&self) -> String {
+        String::from("be ok")
+    }
+}
+
+pub struct BeErr;
+
+/// Matches `Err` results
+pub fn be_err() -> BeErr {
+    BeErr
+}
+
+impl<V, E> Matcher<Result<V, E>> for BeErr {
+    fn matches(&self, actual: &Result<V, E>) -> bool {
+        actual.is_err()
+    }
+
+    fn description(&self) -> String {
+        String::from("be an error")
+    }
+}
+
+pub struct BeSome;
+
+/// Matches `Some` options
+pub fn be_some() -> BeSome {
+    BeSome
+}
+
+impl<V> Matcher<Option<V>> for BeSome {
+    fn matches(&self, actual: &Option<V>) -> bool {
+        actual.is_some()
+    }
+
+    fn description(&self) -> String {
+        String::from("be some")
+    }
+}
+
+pub struct BeNone;
+
+/// Matches `None` options
+pub fn be_none() -> BeNone {
+    BeNone
+}
+
+impl<V> Matcher<Option<V>> for BeNone {
+    fn matches(&self, actual: &Option<V>) -> bool {
+        actual.is_none()
+    }
+
+    fn description(&self) -> String {
+        String::from("be none")
+    }
+}
+
+pub struct Satisfy<F>(&'static str, F);
+
+/// Matches values for which the predicate holds, which is described in failure
+/// messages as given (e.g. "be even")
+pub fn satisfy<F>(description: &'static str, predicate: F) -> Satisfy<F> {
+    Satisfy(description, predicate)
+}
+
+impl<T: ?Sized, F: Fn(&T) -> bool> Matcher<T> for Satisfy<F> {
+    fn matches(&self, actual: &T) -> bool {
+        (self.1)(actual)
+    }
+
+    fn description(&self) -> String {
+        self.0.to_string()
+    }
+}
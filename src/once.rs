@@ -0,0 +1,176 @@
+//! Defines the state behind `before_all` and `after_all` blocks, which run once for all the tests
+//! of a block
+
+/// A value that's initialized once by a future, without holding a lock while it's pending
+pub struct OnceAsync<T> {
+    value: std::sync::OnceLock<T>,
+    /// Whether a test is initializing the value, along with the wakers of the tests waiting for it
+    state: std::sync::Mutex<(bool, Vec<std::task::Waker>)>,
+}
+
+impl<T> OnceAsync<T> {
+    pub const fn new() -> Self {
+        OnceAsync {
+            value: std::sync::OnceLock::new(),
+            state: std::sync::Mutex::new((false, Vec::new())),
+        }
+    }
+
+    /// The value, if it's been initialized
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    /// The value, which is initialized by `init` unless another test is initializing it already,
+    /// in which case this waits for that test to finish, retrying if it fails
+    pub async fn get_or_init<F: std::future::Future<Output = T>>(&self, init: F) -> &T {
+        loop {
+            if let Some(value) = self.value.get() {
+                return value;
+            }
+            if self.claim() {
+                break;
+            }
+            Initialized(self).await;
+        }
+
+        // Let the waiting tests retry if initialization panics or is cancelled
+        let _initializing = Initializing(self);
+        let _ = self.value.set(init.await);
+        self.value.get().unwrap()
+    }
+
+    /// Whether this test gets to initialize the value
+    fn claim(&self) -> bool {
+        let mut state = self.lock();
+        !std::mem::replace(&mut state.0, true)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (bool, Vec<std::task::Waker>)> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl<T> Default for OnceAsync<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wakes the tests waiting for a value once the test initializing it is done, whether it succeeded
+/// or not
+struct Initializing<'a, T>(&'a OnceAsync<T>);
+
+impl<T> Drop for Initializing<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        state.0 = false;
+        for waker in state.1.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// A future that's ready once no test is initializing a value
+struct Initialized<'a, T>(&'a OnceAsync<T>);
+
+impl<T> std::future::Future for Initialized<'_, T> {
+    type Output = ();
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        context: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<()> {
+        let mut state = self.0.lock();
+        if state.0 {
+            state.1.push(context.waker().clone());
+            std::task::Poll::Pending
+        } else {
+            std::task::Poll::Ready(())
+        }
+    }
+}
+
+/// Which tests the test harness runs, according to the arguments of the test binary
+struct Selection {
+    filters: Vec<String>,
+    skips: Vec<String>,
+    exact: bool,
+    /// Whether only ignored tests run (`Some(true)`), every test runs (`None`), or only tests that
+    /// aren't ignored run (`Some(false)`)
+    ignored: Option<bool>,
+}
+
+impl Selection {
+    /// Reads the arguments the test harness filters tests by, skipping the values of its other
+    /// options
+    fn parse(args: impl Iterator<Item = String>) -> Self {
+        let mut selection = Selection {
+            filters: Vec::new(),
+            skips: Vec::new(),
+            exact: false,
+            ignored: Some(false),
+        };
+
+        let mut args = args.skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--" => selection.filters.extend(args.by_ref()),
+                "--exact" => selection.exact = true,
+                "--ignored" => selection.ignored = Some(true),
+                "--include-ignored" => selection.ignored = None,
+                "--skip" => selection.skips.extend(args.next()),
+                "--logfile" | "--test-threads" | "--color" | "--format" | "--shuffle-seed"
+                | "-Z" => {
+                    args.next();
+                }
+                _ => match arg.strip_prefix("--skip=") {
+                    Some(skip) => selection.skips.push(skip.to_string()),
+                    None if !arg.starts_with('-') => selection.filters.push(arg),
+                    None => {}
+                },
+            }
+        }
+
+        selection
+    }
+
+    fn matches(&self, filter: &str, name: &str) -> bool {
+        if self.exact {
+            name == filter
+        } else {
+            name.contains(filter)
+        }
+    }
+
+    /// Whether the test with the given name runs
+    fn runs(&self, name: &str, is_ignored: bool) -> bool {
+        self.ignored.map_or(true, |ignored| ignored == is_ignored)
+            && (self.filters.is_empty()
+                || self.filters.iter().any(|filter| self.matches(filter, name)))
+            && !self.skips.iter().any(|skip| self.matches(skip, name))
+    }
+}
+
+/// Counts the tests that run among the given ones, which are named relative to the module at
+/// `module_path` and paired with whether they're ignored
+pub fn selected(module_path: &str, tests: &[(&str, bool)]) -> usize {
+    static SELECTION: std::sync::OnceLock<Selection> = std::sync::OnceLock::new();
+    let selection = SELECTION.get_or_init(|| Selection::parse(std::env::args()));
+
+    // Tests are named after their path within the crate
+    let module = module_path
+        .split_once("::")
+        .map_or("", |(_, module)| module);
+    tests
+        .iter()
+        .filter(|(name, is_ignored)| {
+            let name = if module.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}::{}", module, name)
+            };
+            selection.runs(&name, *is_ignored)
+        })
+        .count()
+}
@@ -0,0 +1,77 @@
+//! Defines how the outcome of a test is determined from its result, as passed to `after` blocks
+
+/// How a test finished, as passed to `after |outcome| {}` blocks
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The test finished successfully
+    Passed,
+    /// The test returned an `Err`, containing its `Debug` representation
+    Errored(String),
+    /// The test panicked, containing the panic message
+    Panicked(String),
+}
+
+impl Outcome {
+    /// Determines the outcome of a test from its captured result
+    pub fn new<T: Report>(result: &std::thread::Result<T>) -> Self {
+        match result {
+            Ok(value) => match value.error() {
+                Some(error) => Outcome::Errored(error),
+                None => Outcome::Passed,
+            },
+            Err(panic) => Outcome::Panicked(panic_message(&**panic)),
+        }
+    }
+
+    /// Whether the test finished successfully
+    pub fn passed(&self) -> bool {
+        *self == Outcome::Passed
+    }
+
+    /// Whether the test returned an `Err` or panicked
+    pub fn failed(&self) -> bool {
+        !self.passed()
+    }
+
+    /// Whether the test panicked
+    pub fn panicked(&self) -> bool {
+        matches!(self, Outcome::Panicked(_))
+    }
+
+    /// The error or panic message of a failed test
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Outcome::Passed => None,
+            Outcome::Errored(message) | Outcome::Panicked(message) => Some(message),
+        }
+    }
+}
+
+/// The return types of tests, which may report an error
+pub trait Report {
+    /// The `Debug` representation of the error, if any
+    fn error(&self) -> Option<String>;
+}
+
+impl Report for () {
+    fn error(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T, E: std::fmt::Debug> Report for Result<T, E> {
+    fn error(&self) -> Option<String> {
+        self.as_ref().err().map(|error| format!("{:?}", error))
+    }
+}
+
+/// Extracts the message of a panic payload
+pub fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
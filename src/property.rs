@@ -0,0 +1,469 @@
+//! Defines how `forall` property tests generate their inputs from a seeded pseudo-random number
+//! generator, and shrink the inputs of failed runs
+
+use crate::outcome::{Outcome, Report};
+use crate::runtime::catch_unwind;
+
+/// How many inputs a property test is run for, unless set by the environment variable
+const CASES: u32 = 100;
+
+/// The largest size of generated inputs, e.g. the length of collections
+const MAX_SIZE: usize = 100;
+
+/// How many failed runs are shrunk before the smallest input so far is reported
+const MAX_SHRINKS: u32 = 1000;
+
+/// A SplitMix64 pseudo-random number generator
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from zero up to, but not including, the bound (or zero if it's zero)
+    pub fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            0
+        } else {
+            self.next_u64() % bound
+        }
+    }
+}
+
+/// Generates the inputs of a property test, and the smaller inputs to try when one fails
+pub trait Strategy {
+    type Value: Clone + std::fmt::Debug;
+
+    /// Generates an input, whose size (e.g. the length of a collection) is at most `size`
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value;
+
+    /// Smaller variants of a failed input, simplest first
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/// The types that can be generated without a strategy, as in `forall (name: Type)`
+pub trait Arbitrary: Clone + std::fmt::Debug + Sized {
+    fn generate(rng: &mut Rng, size: usize) -> Self;
+
+    fn shrink(&self) -> Vec<Self>;
+}
+
+/// The strategy of an `Arbitrary` type
+pub struct Any<T>(std::marker::PhantomData<fn() -> T>);
+
+pub fn any<T: Arbitrary>() -> Any<T> {
+    Any(std::marker::PhantomData)
+}
+
+impl<T: Arbitrary> Strategy for Any<T> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> T {
+        T::generate(rng, size)
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        value.shrink()
+    }
+}
+
+/// Shrinks an integer towards zero
+fn shrink_integer(value: i128) -> Vec<i128> {
+    let closer = value - value.signum();
+    let mut candidates = vec![0, value / 2, closer];
+    candidates.dedup();
+    candidates.retain(|candidate| *candidate != value);
+    candidates
+}
+
+macro_rules! integers {
+    ($($ty:ty),*) => {$(
+        impl Arbitrary for $ty {
+            fn generate(rng: &mut Rng, size: usize) -> Self {
+                // Half of the values are small, the others are from the whole range
+                if rng.below(2) == 0 {
+                    let value = rng.below(size as u64 + 1) as i128;
+                    let value = if rng.below(2) == 0 { value } else { -value };
+                    <$ty as std::convert::TryFrom<i128>>::try_from(value).unwrap_or(value.unsigned_abs() as $ty)
+                } else {
+                    rng.next_u64() as $ty
+                }
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                shrink_integer(*self as i128)
+                    .into_iter()
+                    .map(|value| value as $ty)
+                    .collect()
+            }
+        }
+
+        impl Strategy for std::ops::Range<$ty> {
+            type Value = $ty;
+
+            fn generate(&self, rng: &mut Rng, _size: usize) -> $ty {
+                assert!(self.start < self.end, "cannot generate from an empty range");
+                let length = (self.end as i128 - self.start as i128) as u64;
+                (self.start as i128 + rng.below(length) as i128) as $ty
+            }
+
+            fn shrink(&self, value: &$ty) -> Vec<$ty> {
+                shrink_integer(*value as i128 - self.start as i128)
+                    .into_iter()
+                    .map(|offset| (self.start as i128 + offset) as $ty)
+                    .collect()
+            }
+        }
+
+        impl Strategy for std::ops::RangeInclusive<$ty> {
+            type Value = $ty;
+
+            fn generate(&self, rng: &mut Rng, _size: usize) -> $ty {
+                let (start, end) = (*self.start() as i128, *self.end() as i128);
+                assert!(start <= end, "cannot generate from an empty range");
+                let offset = match <u64 as std::convert::TryFrom<i128>>::try_from(end - start + 1) {
+                    Ok(length) => rng.below(length),
+                    Err(_) => rng.next_u64(),
+                };
+                (start + offset as i128) as $ty
+            }
+
+            fn shrink(&self, value: &$ty) -> Vec<$ty> {
+                let start = *self.start() as i128;
+                shrink_integer(*value as i128 - start)
+                    .into_iter()
+                    .map(|offset| (start + offset) as $ty)
+                    .collect()
+            }
+        }
+    )*};
+}
+
+integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Arbitrary for bool {
+    fn generate(rng: &mut Rng, _size: usize) -> Self {
+        rng.below(2) == 1
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Arbitrary for char {
+    fn generate(rng: &mut Rng, _size: usize) -> Self {
+        // Most characters are printable ASCII, the others are from the whole range
+        if rng.below(4) != 0 {
+            (b' ' + rng.below(95) as u8) as char
+        } else {
+            loop {
+                if let Some(c) = std::char::from_u32(rng.below(0x11_0000) as u32) {
+                    break c;
+                }
+            }
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self == 'a' {
+            Vec::new()
+        } else {
+            vec!['a']
+        }
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn generate(rng: &mut Rng, size: usize) -> Self {
+        let length = rng.below(size as u64 + 1) as usize;
+        (0..length).map(|_| T::generate(rng, size)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.is_empty() {
+            return candidates;
+        }
+
+        // Remove halves of the elements, then single elements, then shrink each element
+        candidates.push(Vec::new());
+        if self.len() > 2 {
+            candidates.push(self[..self.len() / 2].to_vec());
+            candidates.push(self[self.len() / 2..].to_vec());
+        }
+        for index in 0..self.len() {
+            let mut candidate = self.clone();
+            candidate.remove(index);
+            candidates.push(candidate);
+        }
+        for (index, element) in self.iter().enumerate() {
+            for shrunk in element.shrink() {
+                let mut candidate = self.clone();
+                candidate[index] = shrunk;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl Arbitrary for String {
+    fn generate(rng: &mut Rng, size: usize) -> Self {
+        Vec::<char>::generate(rng, size).into_iter().collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        self.chars()
+            .collect::<Vec<_>>()
+            .shrink()
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn generate(rng: &mut Rng, size: usize) -> Self {
+        if rng.below(4) == 0 {
+            None
+        } else {
+            Some(T::generate(rng, size))
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            Some(value) => std::iter::once(None)
+                .chain(value.shrink().into_iter().map(Some))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// The inputs of a property test, which are described by name when it fails
+pub trait Inputs {
+    fn describe(&self, names: &[&str]) -> String;
+}
+
+macro_rules! tuples {
+    ($(($($name:ident $index:tt),*)),*) => {$(
+        impl<$($name: Arbitrary),*> Arbitrary for ($($name,)*) {
+            fn generate(rng: &mut Rng, size: usize) -> Self {
+                ($($name::generate(rng, size),)*)
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = Vec::new();
+                $(
+                    for shrunk in self.$index.shrink() {
+                        let mut candidate = self.clone();
+                        candidate.$index = shrunk;
+                        candidates.push(candidate);
+                    }
+                )*
+                candidates
+            }
+        }
+
+        impl<$($name: std::fmt::Debug),*> Inputs for ($($name,)*) {
+            fn describe(&self, names: &[&str]) -> String {
+                let inputs: &[&dyn std::fmt::Debug] = &[$(&self.$index),*];
+                names
+                    .iter()
+                    .zip(inputs)
+                    .map(|(name, input)| format!("{} = {:?}", name, input))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        }
+
+        impl<$($name: Strategy),*> Strategy for ($($name,)*) {
+            type Value = ($($name::Value,)*);
+
+            fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+                ($(self.$index.generate(rng, size),)*)
+            }
+
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut candidates = Vec::new();
+                $(
+                    for shrunk in self.$index.shrink(&value.$index) {
+                        let mut candidate = value.clone();
+                        candidate.$index = shrunk;
+                        candidates.push(candidate);
+                    }
+                )*
+                candidates
+            }
+        }
+    )*};
+}
+
+tuples!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+);
+
+/// Reads a number from an environment variable, if it's set
+fn from_env<T: std::str::FromStr>(variable: &str) -> Option<T> {
+    let value = std::env::var(variable).ok()?;
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => panic!("`{}` is not a valid value of {}", value, variable),
+    }
+}
+
+/// The seed of the inputs, which is random unless set by the environment variable
+fn seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    from_env("DEMONSTRATE_SEED").unwrap_or_else(|| {
+        std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish()
+    })
+}
+
+/// The failure message of a run of a property test, if it failed
+fn failure<T: Report>(result: &std::thread::Result<T>) -> Option<String> {
+    Outcome::new(result).message().map(str::to_string)
+}
+
+/// Reports the smallest input a property test was found to fail for
+fn report_counterexample(
+    description: &str,
+    seed: u64,
+    case: u32,
+    shrinks: u32,
+    input: &str,
+    message: &str,
+) -> ! {
+    panic!(
+        "`{}` failed for {} (found in case {} with the seed {} and shrunk {} \
+         times, rerun with DEMONSTRATE_SEED={} to reproduce): {}",
+        description, input, case, seed, shrinks, seed, message
+    )
+}
+
+/// Runs a property test for each of its generated inputs, shrinking the first input it
+/// fails for towards the smallest one it still fails for
+pub fn forall<S: Strategy, T: Report>(
+    description: &str,
+    names: &[&str],
+    strategy: S,
+    test: impl Fn(S::Value) -> T,
+) -> T
+where
+    S::Value: Inputs,
+{
+    let seed = seed();
+    let mut rng = Rng::new(seed);
+    let run = |input: &S::Value| {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| test(input.clone())))
+    };
+
+    let cases = from_env("DEMONSTRATE_CASES").unwrap_or(CASES).max(1);
+    let mut output = None;
+    for case in 1..=cases {
+        let input = strategy.generate(&mut rng, (case as usize).min(MAX_SIZE));
+        let result = run(&input);
+        let mut message = match failure(&result) {
+            Some(message) => message,
+            None => {
+                output = result.ok();
+                continue;
+            }
+        };
+
+        // Move to the first smaller input that still fails, until none do
+        let mut input = input;
+        let mut shrinks = 0;
+        'shrinking: while shrinks < MAX_SHRINKS {
+            for candidate in strategy.shrink(&input) {
+                if let Some(candidate_message) = failure(&run(&candidate)) {
+                    input = candidate;
+                    message = candidate_message;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        let input = input.describe(names);
+        report_counterexample(description, seed, case, shrinks, &input, &message);
+    }
+
+    output.expect("a property test runs at least once")
+}
+
+/// Runs an `async` property test for each of its generated inputs, shrinking the first
+/// input it fails for, whose output is given explicitly so that the `?` operator can be
+/// used within `async` blocks
+pub async fn forall_async<T: Report, S: Strategy, F, G>(
+    description: &str,
+    names: &[&str],
+    strategy: S,
+    test: G,
+) -> T
+where
+    S::Value: Inputs,
+    F: std::future::Future<Output = T>,
+    G: Fn(S::Value) -> F,
+{
+    let seed = seed();
+    let mut rng = Rng::new(seed);
+
+    let cases = from_env("DEMONSTRATE_CASES").unwrap_or(CASES).max(1);
+    let mut output = None;
+    for case in 1..=cases {
+        let input = strategy.generate(&mut rng, (case as usize).min(MAX_SIZE));
+        let result = catch_unwind::<T, _>(test(input.clone())).await;
+        let mut message = match failure(&result) {
+            Some(message) => message,
+            None => {
+                output = result.ok();
+                continue;
+            }
+        };
+
+        let mut input = input;
+        let mut shrinks = 0;
+        'shrinking: while shrinks < MAX_SHRINKS {
+            for candidate in strategy.shrink(&input) {
+                let result = catch_unwind::<T, _>(test(candidate.clone())).await;
+                if let Some(candidate_message) = failure(&result) {
+                    input = candidate;
+                    message = candidate_message;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        let input = input.describe(names);
+        report_counterexample(description, seed, case, shrinks, &input, &message);
+    }
+
+    output.expect("a property test runs at least once")
+}
@@ -0,0 +1,314 @@
+//! Defines the wrappers that generated tests run within, such as those of their time limits,
+//! retries and concurrency limits
+
+use crate::outcome::{Outcome, Report};
+
+/// A future that catches the panics of the future it wraps
+pub struct CatchUnwind<F>(std::pin::Pin<Box<F>>);
+
+impl<F: std::future::Future> std::future::Future for CatchUnwind<F> {
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        context: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let future = self.0.as_mut();
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| future.poll(context))) {
+            Ok(std::task::Poll::Pending) => std::task::Poll::Pending,
+            Ok(std::task::Poll::Ready(output)) => std::task::Poll::Ready(Ok(output)),
+            Err(panic) => std::task::Poll::Ready(Err(panic)),
+        }
+    }
+}
+
+/// Catches the panics of a future, whose output is given explicitly so that the
+/// `?` operator can be used within `async` blocks
+pub fn catch_unwind<T, F: std::future::Future<Output = T>>(future: F) -> CatchUnwind<F> {
+    CatchUnwind(Box::pin(future))
+}
+
+/// The return types of tests with a timeout, which run on a separate thread that sends back the
+/// `Debug` representation of their error rather than the return value, so that it needn't be `Send`
+pub trait Timed: Report {
+    /// The return type of the test function, which reports the same error
+    type Output;
+
+    /// The return value of a test that finished in time with the given error, if any
+    fn output(error: Option<String>) -> Self::Output;
+}
+
+impl Timed for () {
+    type Output = ();
+
+    fn output(_error: Option<String>) {}
+}
+
+impl<T, E: std::fmt::Debug> Timed for Result<T, E> {
+    type Output = Result<(), Failure>;
+
+    fn output(error: Option<String>) -> Result<(), Failure> {
+        error.map_or(Ok(()), |error| Err(Failure(error)))
+    }
+}
+
+/// The error of a test with a timeout, whose `Debug` representation is that of the original error
+pub struct Failure(String);
+
+impl std::fmt::Debug for Failure {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+/// Runs a test on a separate thread, failing it once it doesn't finish within the time limit
+pub fn timeout<T: Timed>(
+    limit: std::time::Duration,
+    description: &str,
+    test: impl FnOnce() -> T + Send + 'static,
+) -> T::Output {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut builder = std::thread::Builder::new();
+    if let Some(name) = std::thread::current().name() {
+        builder = builder.name(name.to_string());
+    }
+    builder
+        .spawn(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| test().error()));
+            let _ = sender.send(result);
+        })
+        .expect("failed to spawn the thread of a test with a timeout");
+
+    match receiver.recv_timeout(limit) {
+        Ok(Ok(error)) => T::output(error),
+        Ok(Err(panic)) => std::panic::resume_unwind(panic),
+        Err(_) => panic!("`{}` exceeded its timeout of {:?}", description, limit),
+    }
+}
+
+/// A future that fails once the future it wraps has been pending past its deadline
+pub struct Deadline<F> {
+    future: std::pin::Pin<Box<F>>,
+    limit: std::time::Duration,
+    description: &'static str,
+    waker: Option<std::sync::Arc<std::sync::Mutex<(bool, std::task::Waker)>>>,
+}
+
+impl<F: std::future::Future> std::future::Future for Deadline<F> {
+    type Output = F::Output;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        context: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        // Wake the task once the deadline has passed, so that it's polled again
+        match &self.waker {
+            Some(waker) => {
+                let mut waker = waker.lock().unwrap();
+                if waker.0 {
+                    panic!(
+                        "`{}` exceeded its timeout of {:?}",
+                        self.description, self.limit
+                    );
+                }
+                waker.1 = context.waker().clone();
+            }
+            None => {
+                let waker =
+                    std::sync::Arc::new(std::sync::Mutex::new((false, context.waker().clone())));
+                let limit = self.limit;
+                let timer = waker.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(limit);
+                    let mut waker = timer.lock().unwrap();
+                    waker.0 = true;
+                    waker.1.wake_by_ref();
+                });
+                self.waker = Some(waker);
+            }
+        }
+
+        self.future.as_mut().poll(context)
+    }
+}
+
+/// Fails a future once it's been pending past its time limit, whose output is given
+/// explicitly so that the `?` operator can be used within `async` blocks
+pub fn deadline<T, F: std::future::Future<Output = T>>(
+    limit: std::time::Duration,
+    description: &'static str,
+    future: F,
+) -> Deadline<F> {
+    Deadline {
+        future: Box::pin(future),
+        limit,
+        description,
+        waker: None,
+    }
+}
+
+/// Writes a message about a test to the standard error directly, so that it's shown even when
+/// the test's output is captured
+fn report(message: &str) {
+    use std::io::Write;
+
+    let _ = writeln!(std::io::stderr(), "{}", message);
+}
+
+/// Reports a failed attempt at a test
+fn report_attempt(description: &str, attempt: u32, attempts: u32, message: &str) {
+    report(&format!(
+        "`{}` failed attempt {} of {}: {}",
+        description, attempt, attempts, message
+    ));
+}
+
+/// Runs a test until it passes, making at most `retries` more attempts after the first
+pub fn retry<T: Report>(retries: u32, description: &str, mut test: impl FnMut() -> T) -> T {
+    let attempts = retries + 1;
+    let mut attempt = 1;
+    loop {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut test));
+        match Outcome::new(&result).message() {
+            Some(message) if attempt < attempts => {
+                report_attempt(description, attempt, attempts, message);
+                attempt += 1;
+            }
+            _ => {
+                return match result {
+                    Ok(output) => output,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+        }
+    }
+}
+
+/// Runs an `async` test until it passes, making at most `retries` more attempts after
+/// the first, whose output is given explicitly so that the `?` operator can be used
+/// within `async` blocks
+pub async fn retry_async<T: Report, F, G>(retries: u32, description: &str, mut test: G) -> T
+where
+    F: std::future::Future<Output = T>,
+    G: FnMut() -> F,
+{
+    let attempts = retries + 1;
+    let mut attempt = 1;
+    loop {
+        let result = catch_unwind::<T, _>(test()).await;
+        match Outcome::new(&result).message() {
+            Some(message) if attempt < attempts => {
+                report_attempt(description, attempt, attempts, message);
+                attempt += 1;
+            }
+            _ => {
+                return match result {
+                    Ok(output) => output,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+        }
+    }
+}
+
+/// A semaphore limiting how many tests sharing a key may run at once
+struct Semaphore {
+    state: std::sync::Mutex<SemaphoreState>,
+    finished: std::sync::Condvar,
+}
+
+/// How many tests sharing a key are running, and how many may run at once, which is the smallest
+/// limit any of them has declared
+struct SemaphoreState {
+    running: u32,
+    max: u32,
+}
+
+/// The semaphores of the keys that tests have used so far
+type Semaphores = Vec<(&'static str, std::sync::Arc<Semaphore>)>;
+
+/// The semaphore of each key, which every test within the process shares and the first test
+/// using it creates
+static SEMAPHORES: std::sync::Mutex<Semaphores> = std::sync::Mutex::new(Vec::new());
+
+/// Allows a test to run while it's held
+pub struct Permit(std::sync::Arc<Semaphore>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self
+            .0
+            .state
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        state.running -= 1;
+        self.0.finished.notify_all();
+    }
+}
+
+/// Waits until fewer than `max` tests sharing the key are running, or fewer than the smallest
+/// limit another test sharing the key has declared
+pub fn acquire(key: &'static str, max: u32) -> Permit {
+    let semaphore = {
+        let mut semaphores = SEMAPHORES.lock().unwrap_or_else(|error| error.into_inner());
+        match semaphores.iter().find(|(other, _)| *other == key) {
+            Some((_, semaphore)) => semaphore.clone(),
+            None => {
+                let semaphore = std::sync::Arc::new(Semaphore {
+                    state: std::sync::Mutex::new(SemaphoreState { running: 0, max }),
+                    finished: std::sync::Condvar::new(),
+                });
+                semaphores.push((key, semaphore.clone()));
+                semaphore
+            }
+        }
+    };
+
+    let mut state = semaphore
+        .state
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    state.max = state.max.min(max);
+    while state.running >= state.max {
+        state = semaphore
+            .finished
+            .wait(state)
+            .unwrap_or_else(|error| error.into_inner());
+    }
+    state.running += 1;
+    drop(state);
+
+    Permit(semaphore)
+}
+
+/// The result of a test run by an `around` block, which fails the test if the block
+/// didn't run it
+pub fn ran_around<T>(output: Option<T>, description: &str) -> T {
+    match output {
+        Some(output) => output,
+        None => panic!("An `around` block of `{}` didn't call `run()`", description),
+    }
+}
+
+/// Wakes the thread that's blocked on a future
+struct ThreadWaker(std::thread::Thread);
+
+impl std::task::Wake for ThreadWaker {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Drives a future to completion on the current thread, which runs `async` tests that
+/// don't use an async runtime
+pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = std::task::Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            std::task::Poll::Ready(output) => return output,
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Defines the `expect_snapshot!` macro and the items backing it, which compare values against the
+//! snapshots recorded for each test
+
+/// Asserts that the `Debug` representation of a value, or its `Display` representation when given
+/// `%value`, matches the snapshot of it recorded for the `demonstrate!` test it's used within
+#[macro_export]
+macro_rules! expect_snapshot {
+    (% $value:expr $(,)?) => {
+        __SNAPSHOTS.assert(&format!("{}", $value))
+    };
+    ($value:expr $(,)?) => {
+        __SNAPSHOTS.assert(&format!("{:#?}", $value))
+    };
+}
+
+/// The snapshots of a test, which are stored within the `snapshots` directory of its crate
+/// at the path of its module, and named after its function and the order they're taken in
+pub struct Snapshots {
+    directory: &'static str,
+    module: &'static str,
+    test: &'static str,
+    description: &'static str,
+    taken: std::sync::atomic::AtomicUsize,
+}
+
+impl Snapshots {
+    pub const fn new(
+        directory: &'static str,
+        module: &'static str,
+        test: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Snapshots {
+            directory,
+            module,
+            test,
+            description,
+            taken: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    /// Starts taking the snapshots from the first again, for another run of the test
+    pub fn reset(&self) {
+        self.taken.store(0, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// The path of the snapshot taken in the given order, e.g.
+    /// `snapshots/tests/math/adds-2.snap` for the second one of `tests::math::adds`
+    fn path(&self, order: usize) -> std::path::PathBuf {
+        let mut path = std::path::Path::new(self.directory).join("snapshots");
+        path.extend(self.module.split("::"));
+        if order == 1 {
+            path.join(format!("{}.snap", self.test))
+        } else {
+            path.join(format!("{}-{}.snap", self.test, order))
+        }
+    }
+
+    /// Compares the next snapshot with its recording, recording it instead if it's the
+    /// first time it's taken or `DEMONSTRATE_UPDATE` is set
+    pub fn assert(&self, actual: &str) {
+        let order = self.taken.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        let path = self.path(order);
+        let actual = actual.replace("\r\n", "\n");
+        let update = std::env::var_os("DEMONSTRATE_UPDATE")
+            .is_some_and(|update| !update.is_empty() && update != "0");
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(expected) if !update => expected.replace("\r\n", "\n"),
+            Err(error) if !update && error.kind() != std::io::ErrorKind::NotFound => {
+                panic!(
+                    "Failed to read the snapshot at {}: {}",
+                    path.display(),
+                    error
+                )
+            }
+            _ => {
+                // New snapshots must be committed beforehand for CI to check them
+                let is_ci = std::env::var_os("CI")
+                    .is_some_and(|ci| !ci.is_empty() && ci != "false" && ci != "0");
+                if !update && is_ci {
+                    panic!(
+                        "`{}` has no snapshot at {} (run it with DEMONSTRATE_UPDATE=1 \
+                         outside of CI to record it)",
+                        self.description,
+                        path.display()
+                    );
+                }
+                return record(&path, &actual);
+            }
+        };
+
+        let expected = expected.trim_end_matches('\n');
+        let actual = actual.trim_end_matches('\n');
+        if expected != actual {
+            panic!(
+                "`{}` doesn't match its snapshot at {} (rerun with DEMONSTRATE_UPDATE=1 \
+                 to update it)\n--- snapshot\n+++ actual\n{}",
+                self.description,
+                path.display(),
+                diff(expected, actual)
+            );
+        }
+    }
+}
+
+/// Writes a snapshot, creating the directories it's within
+fn record(path: &std::path::Path, snapshot: &str) {
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, format!("{}\n", snapshot.trim_end_matches('\n'))));
+    if let Err(error) = written {
+        panic!(
+            "Failed to write the snapshot at {}: {}",
+            path.display(),
+            error
+        );
+    }
+}
+
+/// A line diff between a snapshot and its actual value, marking the lines only found in
+/// the snapshot with `-` and the lines only found in the actual value with `+`
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    // The length of the longest common subsequence of each pair of suffixes
+    let mut common = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push(format!("  {}", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            lines.push(format!("- {}", expected[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", actual[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
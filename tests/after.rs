@@ -0,0 +1,134 @@
+use demonstrate::demonstrate;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PANICKED: AtomicUsize = AtomicUsize::new(0);
+static RETURNED: AtomicUsize = AtomicUsize::new(0);
+
+struct Flag<'a>(&'a AtomicUsize);
+
+impl Drop for Flag<'_> {
+    fn drop(&mut self) {
+        // Runs after the test's `after` block, which must have set the flag
+        assert_eq!(self.0.load(Ordering::SeqCst), 1);
+    }
+}
+
+demonstrate! {
+    describe "after" {
+        use super::*;
+
+        context "panicking" {
+            before {
+                let _flag = Flag(&PANICKED);
+            }
+
+            after {
+                PANICKED.fetch_add(1, Ordering::SeqCst);
+            }
+
+            #[should_panic(expected = "expected failure")]
+            it "still runs after" {
+                panic!("expected failure")
+            }
+        }
+
+        context "returning" -> Result<(), &'static str> {
+            before {
+                let _flag = Flag(&RETURNED);
+            }
+
+            after {
+                RETURNED.fetch_add(1, Ordering::SeqCst);
+            }
+
+            it "returns early" {
+                if RETURNED.load(Ordering::SeqCst) == 0 {
+                    return Ok(());
+                }
+                Err("after ran before the test finished")
+            }
+        }
+
+        context "outcome" {
+            after |outcome| {
+                assert_eq!(outcome.passed(), !outcome.failed());
+            }
+
+            context "passing" {
+                after |result| {
+                    assert!(result.passed());
+                    assert_eq!(result.message(), None);
+                }
+
+                it "passes" {
+                    assert!(true)
+                }
+            }
+
+            context "panicking" {
+                after |outcome| {
+                    assert!(outcome.panicked());
+                    assert_eq!(outcome.message(), Some("expected failure"));
+                }
+
+                #[should_panic(expected = "expected failure")]
+                it "panics" {
+                    panic!("expected failure")
+                }
+
+                #[should_panic(expected = "expected failure")]
+                it "panics with a formatted message" {
+                    panic!("expected {}", "failure")
+                }
+            }
+        }
+
+        context "question mark" -> Result<(), String> {
+            before {
+                let mut steps = Vec::new();
+            }
+
+            after {
+                steps.push("after");
+                assert_eq!(steps, ["body", "after"]);
+            }
+
+            it "uses the question mark operator" {
+                steps.push("body");
+                "1".parse::<u8>().map_err(|error| error.to_string())?;
+                Ok(())
+            }
+        }
+
+        #[async_attributes::test]
+        async context "asynchronous" -> Result<(), String> {
+            before {
+                let mut steps = Vec::new();
+            }
+
+            after {
+                steps.push("after");
+                assert_eq!(steps, ["body", "after"]);
+            }
+
+            it "ends in a tail expression" {
+                steps.push("body");
+                async_std::task::yield_now().await;
+                Ok(())
+            }
+
+            it "uses the question mark operator" {
+                steps.push("body");
+                "1".parse::<u8>().map_err(|error| error.to_string())?;
+                Ok(())
+            }
+
+            #[should_panic(expected = "async failure")]
+            it "panics" -> () {
+                steps.push("body");
+                async_std::task::yield_now().await;
+                panic!("async failure")
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use demonstrate::demonstrate;
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn record(event: &'static str) {
+    EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+fn events() -> Vec<&'static str> {
+    EVENTS.with(|events| events.borrow().clone())
+}
+
+demonstrate! {
+    describe "around" {
+        use super::*;
+
+        around |run| {
+            record("outer start");
+            run();
+            record("outer end");
+            assert_eq!(
+                events(),
+                vec!["outer start", "before", "test", "after", "outer end"]
+            );
+            EVENTS.with(|events| events.borrow_mut().clear());
+        }
+
+        before {
+            record("before");
+        }
+
+        after {
+            record("after");
+        }
+
+        it "wraps the test and its hooks" {
+            record("test")
+        }
+
+        context "when nested" {
+            around |test| {
+                let _guard = LOCK.lock().unwrap();
+                test();
+            }
+
+            it "composes from the outermost" {
+                assert!(LOCK.try_lock().is_err());
+                record("test")
+            }
+        }
+    }
+
+    describe "around results" -> Result<(), String> {
+        around |run| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(run));
+            assert!(result.is_ok(), "the test panicked");
+        }
+
+        it "returns the result of the test" {
+            let value = "1".parse::<u8>().map_err(|error| error.to_string())?;
+            assert_eq!(value, 1);
+            Ok(())
+        }
+
+        #[should_panic(expected = "the test panicked")]
+        it "can catch panics" -> () {
+            panic!("failure")
+        }
+    }
+
+    describe "sync around" {
+        use super::*;
+
+        around |run| {
+            record("start");
+            run();
+            assert_eq!(events(), vec!["start", "test"]);
+        }
+
+        async it "blocks on async tests" {
+            async_std::task::yield_now().await;
+            record("test")
+        }
+    }
+
+    describe "without running" {
+        around |_run| {}
+
+        #[should_panic(expected = "An `around` block of `without running > fails` didn't call `run()`")]
+        it "fails" {}
+    }
+
+    async describe "async around" {
+        use super::*;
+
+        around |run| {
+            async_std::task::yield_now().await;
+            record("start");
+            run().await;
+            assert_eq!(events(), vec!["start", "test"]);
+        }
+
+        it "awaits the test" {
+            async_std::task::yield_now().await;
+            record("test")
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use demonstrate::demonstrate;
+
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+demonstrate! {
+    describe "cases" {
+        use super::*;
+
+        it "adds" for (a, b, sum) in [(1, 2, 3), (2, 2, 4)] {
+            assert_eq!(add(a, b), sum)
+        }
+
+        #[should_panic]
+        it "overflows" for (a, b) in [(u8::MAX, 1), (1, u8::MAX)] {
+            add(a, b);
+        }
+
+        context "with before" {
+            before {
+                let zero = 0;
+            }
+
+            it "identity" for value in [1, 1 + zero] {
+                assert_eq!(add(value, zero), value)
+            }
+
+            it "is numbered" for value in [zero, zero] {
+                assert_eq!(add(value, 1), 1)
+            }
+        }
+
+        context "returnable" -> Result<(), String> {
+            it "parses" for (text, number) in [("1", 1), ("22", 22)] {
+                assert_eq!(text.parse::<u8>().map_err(|e| e.to_string())?, number);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use demonstrate::demonstrate;
+use std::time::Duration;
+
+async fn double(value: u8) -> u8 {
+    async_std::task::sleep(Duration::from_millis(1)).await;
+    value * 2
+}
+
+demonstrate! {
+    async describe "executor" {
+        use super::*;
+
+        it "runs without a runtime attribute" {
+            assert_eq!(double(2).await, 4)
+        }
+
+        it "returns results" -> Result<(), String> {
+            let value = "2".parse::<u8>().map_err(|error| error.to_string())?;
+            assert_eq!(double(value).await, 4);
+            Ok(())
+        }
+
+        it "has cases" for (value, doubled) in [(1, 2), (3, 6)] {
+            assert_eq!(double(value).await, doubled)
+        }
+
+        #[should_panic(expected = "failure")]
+        it "panics" {
+            double(1).await;
+            panic!("failure")
+        }
+
+        context "with hooks" {
+            before_all {
+                let shared: u8 = double(3).await;
+            }
+
+            after {
+                assert_eq!(double(*shared).await, 12)
+            }
+
+            it "shares values" {
+                assert_eq!(*shared, 6)
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use demonstrate::demonstrate;
+use demonstrate::matchers::*;
+
+mod predicates {
+    pub fn eq(left: u8, right: u8) -> bool {
+        left == right
+    }
+}
+
+fn parse(text: &str) -> Result<u8, std::num::ParseIntError> {
+    text.parse()
+}
+
+demonstrate! {
+    describe "expectations" {
+        use super::*;
+
+        it "matches values" {
+            expect(2 + 3).to(eq(5));
+            expect(String::from("five")).to(eq("five"));
+            expect(5).not_to(eq(4))
+        }
+
+        it "matches collections" {
+            let values = vec![1, 2, 3];
+            expect(&values).to(contain(&3));
+            expect(values).not_to(contain(&4).or(be_empty()));
+            expect(Vec::<u8>::new()).to(be_empty());
+            expect([1, 2]).to(contain(&1))
+        }
+
+        it "matches strings" {
+            expect("hello world").to(contain("world").and(contain(&'h')));
+            expect(String::new()).to(be_empty())
+        }
+
+        it "matches results and options" {
+            expect(parse("1")).to(be_ok());
+            expect(parse("one")).to(be_err());
+            expect(Some(1)).to(be_some());
+            expect(None::<u8>).to(be_none())
+        }
+
+        it "matches floats" {
+            expect(0.1 + 0.2).to(be_close_to(0.3, 1e-9));
+            expect(1.5f32).not_to(be_close_to(1.0, 0.1))
+        }
+
+        it "composes matchers" {
+            expect(5).to(be_greater_than(1).and(be_less_than(10)));
+            expect(5).to(not(eq(4)).and(satisfy("be odd", |n: &i32| n % 2 == 1)))
+        }
+
+        it "keeps methods of the same name" {
+            let value = parse("1").expect("a number");
+            expect(value).to(eq(1))
+        }
+
+        context "within a context" {
+            #[should_panic(expected = "`expectations > within a context > describes failures`")]
+            it "describes failures" {
+                expect(Some(1)).to(be_none())
+            }
+        }
+
+        #[should_panic(expected = "`expectations > describes failures` expected 4 to equal 5")]
+        it "describes failures" {
+            expect(4).to(eq(5))
+        }
+
+        #[should_panic(expected = "expected [1, 2] not to contain 2 and be greater than 1")]
+        it "describes negated failures" {
+            expect(vec![1, 2]).not_to(contain(&2).and(satisfy("be greater than 1", |values: &Vec<i32>| values.len() > 1)))
+        }
+    }
+
+    async describe "async expectations" {
+        use demonstrate::matchers::*;
+
+        #[should_panic(expected = "`async expectations > describes failures` expected 1 to equal 2")]
+        it "describes failures" {
+            async_std::task::yield_now().await;
+            expect(1).to(eq(2))
+        }
+    }
+
+    describe "other matchers" {
+        use super::predicates::*;
+
+        it "aren't ambiguous" {
+            assert!(eq(1, 1))
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use demonstrate::demonstrate;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static BUILT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Default)]
+struct Db {
+    rows: Vec<String>,
+}
+
+impl Db {
+    fn build() -> Self {
+        BUILT.fetch_add(1, Ordering::SeqCst);
+        Db::default()
+    }
+}
+
+struct Expensive;
+
+impl Expensive {
+    fn build() -> Self {
+        panic!("built without being requested")
+    }
+}
+
+demonstrate! {
+    describe "fixtures" {
+        use super::*;
+
+        fixture db: Db = Db::build();
+        fixture expensive: Expensive = Expensive::build();
+        fixture name: String = String::from("row");
+        fixture rows: usize = db.rows.len();
+
+        it "borrows fixtures" |db: &Db, name: &String| {
+            assert!(db.rows.is_empty());
+            assert_eq!(name, "row")
+        }
+
+        it "mutably borrows fixtures" |db: &mut Db| {
+            db.rows.push(String::from("row"));
+            assert_eq!(db.rows.len(), 1)
+        }
+
+        it "takes fixtures" |name: String| {
+            assert_eq!(name + "s", "rows")
+        }
+
+        it "builds the fixtures they depend on" |rows: &usize| {
+            assert_eq!(*rows, 0)
+        }
+
+        it "doesn't build unrequested fixtures" |name: &String| {
+            assert!(!name.is_empty())
+        }
+
+        #[should_panic(expected = "built without being requested")]
+        it "builds requested fixtures" |expensive: &Expensive| {
+            let _ = expensive;
+        }
+
+        it "builds fixtures for each test" |db, name| -> Result<(), String> {
+            assert!(BUILT.load(Ordering::SeqCst) > 0);
+            assert!(db.rows.iter().all(|row| row != name));
+            Ok(())
+        }
+
+        context "with an override" {
+            fixture name: String = String::from("overridden");
+
+            it "uses the closest fixture" |name: &String| {
+                assert_eq!(name, "overridden")
+            }
+        }
+    }
+
+    async describe "async fixtures" {
+        fixture value: u8 = 1;
+
+        it "awaits with fixtures" |value: &u8| {
+            async_std::task::yield_now().await;
+            assert_eq!(*value, 1)
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "focus" {
+        use std::process::Command;
+
+        fit "ignores every unfocused test" {
+            let output = Command::new(std::env::current_exe().unwrap())
+                .args(["not_focused", "keeps_its_own_ignore", "--test-threads=1"])
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8(output.stdout).unwrap();
+
+            let results = stdout.lines().filter_map(|line| line.strip_prefix("test focus::"));
+            assert_eq!(
+                results.collect::<Vec<_>>(),
+                [
+                    "focused::keeps_its_own_ignore ... ignored, slow",
+                    "is_not_focused ... ignored, not focused",
+                    "unfocused::is_not_focused ... ignored, not focused",
+                ]
+            )
+        }
+
+        it "is not focused" {
+            panic!("should have been ignored")
+        }
+
+        context "unfocused" {
+            it "is not focused" {
+                panic!("should have been ignored")
+            }
+        }
+
+        fcontext "focused" {
+            it "runs" {
+                assert_eq!(1 + 1, 2)
+            }
+
+            #[ignore = "slow"]
+            it "keeps its own ignore" {
+                panic!("should have been ignored")
+            }
+        }
+    }
+}
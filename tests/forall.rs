@@ -0,0 +1,70 @@
+use demonstrate::demonstrate;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static BEFORE: AtomicUsize = AtomicUsize::new(0);
+static AFTER: AtomicUsize = AtomicUsize::new(0);
+
+demonstrate! {
+    describe "forall" {
+        use super::*;
+
+        it "reverses twice" forall (v: Vec<u8>, n in 0..100u32) {
+            let mut reversed = v.clone();
+            reversed.reverse();
+            reversed.reverse();
+            assert_eq!(reversed, v);
+            assert!(n < 100)
+        }
+
+        it "stays within inclusive ranges" forall (n in -5..=5i8, c: char) {
+            assert!((-5..=5).contains(&n));
+            assert!(c.len_utf8() <= 4)
+        }
+
+        it "generates optional values" forall (text: String, value: Option<(bool, u64)>) {
+            assert_eq!(text.chars().collect::<String>(), text);
+            let negated = value.map(|(flag, number)| (!flag, number));
+            assert_eq!(negated.map(|(flag, number)| (!flag, number)), value)
+        }
+
+        it "returns errors" -> Result<(), String> forall (n: u16) {
+            let parsed = n.to_string().parse::<u16>().map_err(|error| error.to_string())?;
+            assert_eq!(parsed, n);
+            Ok(())
+        }
+
+        context "with hooks" {
+            before {
+                let offset = BEFORE.fetch_add(1, Ordering::SeqCst);
+            }
+
+            after {
+                AFTER.fetch_add(1, Ordering::SeqCst);
+            }
+
+            it "runs them for each input" forall (n in 0..10usize) {
+                assert!(n < 10);
+                assert_eq!(offset, AFTER.load(Ordering::SeqCst))
+            }
+        }
+
+        #[should_panic(expected = "DEMONSTRATE_SEED")]
+        it "reports the seed of failures" forall (n: u32) {
+            assert!(n < 1000, "too large")
+        }
+
+        #[should_panic(expected = "failed for v = [0, 0]")]
+        it "shrinks failures" forall (v: Vec<u8>) {
+            assert!(v.len() < 2, "too long")
+        }
+    }
+
+    async describe "async forall" {
+        it "awaits each input" -> Result<(), String> forall (n in 1..=100u8) {
+            async_std::task::yield_now().await;
+            let value = n.to_string().parse::<u8>().map_err(|error| error.to_string())?;
+            assert!(value > 0);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use demonstrate::demonstrate;
+use std::collections::{LinkedList, VecDeque};
+
+demonstrate! {
+    describe "collections" for C in [Vec<u8>, VecDeque<u8>, LinkedList<u8>] {
+        use super::*;
+
+        let collection = C::new();
+
+        it "starts empty" {
+            assert!(collection.is_empty())
+        }
+
+        it "extends" for values in [vec![1], vec![1, 2]] {
+            let mut collection = collection;
+            collection.extend(values.clone());
+            assert_eq!(collection.len(), values.len())
+        }
+
+        context "nested" {
+            it "still knows the type" {
+                let mut collection = collection;
+                collection.extend(vec![1, 2, 3].into_iter().collect::<C>());
+                assert_eq!(collection.iter().sum::<u8>(), 6)
+            }
+        }
+    }
+
+    describe "arrays" for const N: usize in [1, 2, 4] {
+        it "has a length" {
+            assert_eq!([0u8; N].len(), N)
+        }
+    }
+
+    describe "duplicates" for const N: u8 in [1, 1] {
+        it "is numbered" {
+            assert_eq!(N, 1)
+        }
+    }
+
+    describe "stack" for T in [Vec<u8>] {
+        use demonstrate::matchers::*;
+
+        #[should_panic(expected = "`stack > vec_u8 > fails` expected 0 to equal 1")]
+        it "fails" {
+            expect(T::new().len()).to(eq(1))
+        }
+    }
+}
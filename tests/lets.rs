@@ -0,0 +1,82 @@
+use demonstrate::demonstrate;
+use std::cell::Cell;
+
+thread_local! {
+    // Each test runs on its own thread
+    static EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+    static READY: Cell<bool> = const { Cell::new(false) };
+}
+
+fn evaluations() -> usize {
+    EVALUATIONS.with(Cell::get)
+}
+
+fn expensive() -> u32 {
+    EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+    42
+}
+
+demonstrate! {
+    describe "lets" {
+        use super::*;
+
+        let width = 2;
+        let height = 3;
+        let area = width * height;
+        let expensive: u32 = expensive();
+
+        it "evaluates dependencies" {
+            assert_eq!(area, 6)
+        }
+
+        it "skips unused lets" {
+            assert_eq!(width, 2);
+            assert_eq!(evaluations(), 0)
+        }
+
+        it "evaluates once" {
+            assert_eq!(expensive + expensive, 84);
+            assert_eq!(evaluations(), 1)
+        }
+
+        it "is used by format strings" {
+            assert_eq!(format!("{area} {width:>height$}"), "6   2")
+        }
+
+        context "overriding" {
+            let height = 10;
+
+            it "updates dependent lets" {
+                assert_eq!(area, 20)
+            }
+
+            it "is used by cases" for (factor, expected) in [(1, 20), (2, 40)] {
+                assert_eq!(area * factor, expected)
+            }
+
+            context "again" {
+                let width = height + 1;
+
+                before {
+                    let doubled = area * 2;
+                }
+
+                it "is used by before blocks" {
+                    assert_eq!(doubled, 220)
+                }
+            }
+        }
+
+        context "with setup" {
+            let ready = READY.with(Cell::get);
+
+            before {
+                READY.with(|cell| cell.set(true));
+            }
+
+            it "is evaluated after before blocks" {
+                assert!(ready)
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use demonstrate::demonstrate;
+
+mod prefixed {
+    use demonstrate::demonstrate;
+
+    demonstrate! {
+        #![module_prefix = "spec_"]
+
+        describe "core" {
+            use std::collections::HashMap;
+
+            it "uses the real crate" {
+                assert!(HashMap::<u8, u8>::new().is_empty())
+            }
+        }
+    }
+}
+
+demonstrate! {
+    describe "std" {
+        use std::collections::HashSet;
+
+        it "uses the real crate" {
+            assert!(HashSet::<u8>::new().is_empty())
+        }
+
+        context "core" {
+            it "still uses the real crate" {
+                assert_eq!(core::cmp::max(1, 2), 2)
+            }
+        }
+    }
+}
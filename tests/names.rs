@@ -0,0 +1,58 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "match" {
+        it "2 + 2 is 4" {
+            assert_eq!(2 + 2, 4)
+        }
+
+        it "" {}
+
+        it "type" {}
+
+        it "café au lait" {}
+
+        it "как дела" {}
+
+        it "🦀" {}
+
+        it r#"says "hello""# {}
+
+        it "escapes\ttabs" {}
+
+        it "isn't confused by apostrophes" {}
+
+        it "+++" for value in [1, 2] {
+            assert!(value > 0)
+        }
+
+        it "generates the expected paths" {
+            let _: &[fn()] = &[
+                crate::match_::_2_2_is_4,
+                crate::match_::unnamed,
+                crate::match_::type_,
+                crate::match_::cafe_au_lait,
+                crate::match_::kak_dela,
+                crate::match_::u1f980,
+                crate::match_::says_hello,
+                crate::match_::escapes_tabs,
+                crate::match_::isnt_confused_by_apostrophes,
+                crate::match_::unnamed_with_1,
+                crate::match_::unnamed_with_2,
+            ];
+        }
+
+        context "super" {
+            it "self" {
+                let _: fn() = crate::match_::super_::self_;
+            }
+        }
+
+        context "crate" for const N: u8 in [1] {
+            it "9" {
+                assert_eq!(N, 1);
+                let _: fn() = crate::match_::crate_::n_1::_9;
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// The guards of `after_all` blocks are declared within the block of each test
+#![deny(unused_braces)]
+
+use demonstrate::demonstrate;
+use std::io::Write;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SETUPS: AtomicUsize = AtomicUsize::new(0);
+static NESTED_SETUPS: AtomicUsize = AtomicUsize::new(0);
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+static ASYNC_SETUPS: AtomicUsize = AtomicUsize::new(0);
+
+async fn fetch_name() -> String {
+    String::from("fixture")
+}
+
+/// Runs the tests of this binary selected by `args`, returning whether the `after_all` block of
+/// `filtered` ran
+fn tears_down(args: &[&str]) -> bool {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("tore down `filtered`")
+}
+
+demonstrate! {
+    describe "once hooks" {
+        use super::*;
+
+        before_all {
+            SETUPS.fetch_add(1, Ordering::SeqCst);
+            let numbers: Vec<u8> = vec![1, 2, 3];
+        }
+
+        after_all {
+            assert_eq!(numbers.len(), 3);
+            assert_eq!(RUNS.load(Ordering::SeqCst), 4);
+        }
+
+        after {
+            RUNS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        it "shares values" {
+            assert_eq!(numbers, &vec![1, 2, 3]);
+            assert_eq!(SETUPS.load(Ordering::SeqCst), 1);
+        }
+
+        it "sets up once" for _ in [(), ()] {
+            assert_eq!(SETUPS.load(Ordering::SeqCst), 1);
+        }
+
+        #[ignore]
+        it "is not counted" {
+            assert_eq!(SETUPS.load(Ordering::SeqCst), 0);
+        }
+
+        context "nested" {
+            before_all {
+                NESTED_SETUPS.fetch_add(1, Ordering::SeqCst);
+                let total: u8 = numbers.iter().sum();
+            }
+
+            after_all {
+                assert_eq!(total, &6);
+            }
+
+            it "reads outer values" {
+                assert_eq!(*total, 6);
+                assert_eq!(numbers.len(), 3);
+                assert_eq!(NESTED_SETUPS.load(Ordering::SeqCst), 1);
+            }
+        }
+    }
+
+    #[async_attributes::test]
+    async describe "async once hooks" {
+        use super::*;
+
+        before_all {
+            ASYNC_SETUPS.fetch_add(1, Ordering::SeqCst);
+            let name: String = fetch_name().await;
+        }
+
+        it "awaits setup" {
+            assert_eq!(name, "fixture");
+            assert_eq!(ASYNC_SETUPS.load(Ordering::SeqCst), 1)
+        }
+
+        it "sets up once" {
+            assert_eq!(ASYNC_SETUPS.load(Ordering::SeqCst), 1)
+        }
+    }
+
+    #[ignore = "run by `runs after the tests that run`"]
+    describe "filtered" {
+        use super::*;
+
+        after_all {
+            // Written to the standard error directly, so that it isn't captured
+            writeln!(std::io::stderr(), "tore down `filtered`").unwrap();
+        }
+
+        it "runs" {}
+
+        it "is filtered out" {}
+
+        context "nested" {
+            it "is filtered out as well" {}
+        }
+    }
+
+    describe "after all" {
+        use super::*;
+
+        it "runs after the tests that run" {
+            assert!(tears_down(&["--ignored", "filtered::runs"]));
+            assert!(tears_down(&["--ignored", "--exact", "filtered::runs"]));
+            assert!(tears_down(&["--include-ignored", "--skip", "filtered_out", "filtered"]));
+            assert!(tears_down(&["--ignored", "filtered"]));
+            assert!(!tears_down(&["filtered"]))
+        }
+    }
+}
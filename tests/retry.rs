@@ -0,0 +1,73 @@
+use demonstrate::demonstrate;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static FLAKY: AtomicUsize = AtomicUsize::new(0);
+static ERRORS: AtomicUsize = AtomicUsize::new(0);
+static BEFORE: AtomicUsize = AtomicUsize::new(0);
+static AFTER: AtomicUsize = AtomicUsize::new(0);
+static EXHAUSTED: AtomicUsize = AtomicUsize::new(0);
+static ASYNC: AtomicUsize = AtomicUsize::new(0);
+static SLOW: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts an attempt, returning whether it's one of the first `failures` attempts
+fn fails(attempts: &AtomicUsize, failures: usize) -> bool {
+    attempts.fetch_add(1, Ordering::SeqCst) < failures
+}
+
+demonstrate! {
+    describe "retry" retry(2) {
+        use super::*;
+
+        it "passes on the last attempt" {
+            assert!(!fails(&FLAKY, 2), "flaky failure");
+            assert_eq!(FLAKY.load(Ordering::SeqCst), 3)
+        }
+
+        it "retries errors" -> Result<(), String> {
+            if fails(&ERRORS, 1) {
+                return Err(String::from("flaky error"));
+            }
+            Ok(())
+        }
+
+        context "with hooks" retry(1) {
+            before {
+                BEFORE.fetch_add(1, Ordering::SeqCst);
+            }
+
+            after {
+                AFTER.fetch_add(1, Ordering::SeqCst);
+            }
+
+            it "runs them on each attempt" {
+                if AFTER.load(Ordering::SeqCst) == 0 {
+                    panic!("flaky failure");
+                }
+                assert_eq!(BEFORE.load(Ordering::SeqCst), 2)
+            }
+        }
+
+        it "retries timeouts" timeout(50ms) {
+            if fails(&SLOW, 1) {
+                std::thread::sleep(std::time::Duration::from_secs(5));
+            }
+        }
+
+        #[should_panic(expected = "always fails")]
+        it "fails once out of attempts" {
+            assert!(EXHAUSTED.fetch_add(1, Ordering::SeqCst) < 3);
+            panic!("always fails")
+        }
+    }
+
+    async describe "async retry" retry(1) {
+        use super::*;
+
+        it "passes on the last attempt" -> Result<(), String> {
+            async_std::task::yield_now().await;
+            let value = "1".parse::<usize>().map_err(|error| error.to_string())?;
+            assert!(!fails(&ASYNC, value), "flaky failure");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Permits are declared within the block of each test, which keeps it from being nested
+#![deny(unused_braces)]
+
+use demonstrate::demonstrate;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::sleep;
+use std::time::Duration;
+
+static SERIAL: AtomicUsize = AtomicUsize::new(0);
+static PORT: AtomicUsize = AtomicUsize::new(0);
+static PARALLEL: AtomicUsize = AtomicUsize::new(0);
+static SIBLINGS: AtomicUsize = AtomicUsize::new(0);
+static DATABASE: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs for a while as one of the running tests counted by `running`, asserting that at most `max`
+/// tests are running at the same time, including itself
+fn run(running: &AtomicUsize, max: usize) {
+    assert!(running.fetch_add(1, Ordering::SeqCst) < max);
+    sleep(Duration::from_millis(20));
+    assert!(running.fetch_sub(1, Ordering::SeqCst) <= max);
+}
+
+demonstrate! {
+    describe "serial" {
+        use super::*;
+
+        context "describe" serial {
+            it "runs alone" {
+                run(&SERIAL, 1)
+            }
+
+            it "also runs alone" {
+                run(&SERIAL, 1)
+            }
+
+            context "nested" {
+                it "runs alone as well" {
+                    run(&SERIAL, 1)
+                }
+            }
+        }
+
+        context "keyed" {
+            context "server" serial("port") {
+                it "binds the port" {
+                    run(&PORT, 1)
+                }
+
+                it "binds the port again" {
+                    run(&PORT, 1)
+                }
+            }
+
+            context "client" {
+                it "connects to the port" serial("port") {
+                    run(&PORT, 1)
+                }
+            }
+        }
+
+        context "tests" {
+            it "runs with its serial sibling" serial {
+                run(&SIBLINGS, 1)
+            }
+
+            it "runs with its other serial sibling" serial {
+                run(&SIBLINGS, 1)
+            }
+
+            it "isn't serial" {}
+        }
+
+        context "limited" parallel(max = 2) {
+            it "runs with another" {
+                run(&PARALLEL, 2)
+            }
+
+            it "runs with another as well" {
+                run(&PARALLEL, 2)
+            }
+
+            it "runs with another too" {
+                run(&PARALLEL, 2)
+            }
+
+            it "runs with another again" parallel("other", max = 4) {}
+        }
+    }
+
+    describe "mixed" {
+        use super::*;
+
+        it "runs alone" serial("database") {
+            run(&DATABASE, 1)
+        }
+
+        context "parallel" parallel("database", max = 3) {
+            it "runs with others" {
+                run(&DATABASE, 3)
+            }
+
+            it "runs with others as well" {
+                run(&DATABASE, 3)
+            }
+
+            it "runs with others too" {
+                run(&DATABASE, 3)
+            }
+        }
+
+        it "are limited with several test threads" {
+            let output = Command::new(std::env::current_exe().unwrap())
+                .args(["--test-threads=8", "--skip", "are_limited_with_several_test_threads"])
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            assert!(output.status.success(), "{}", stdout)
+        }
+    }
+
+    describe "other root" {
+        use super::*;
+
+        it "shares the key of the first" serial("port") {
+            run(&PORT, 1)
+        }
+    }
+}
+
+demonstrate! {
+    describe "other invocation" {
+        use super::*;
+
+        it "shares the key as well" serial("port") {
+            run(&PORT, 1)
+        }
+    }
+}
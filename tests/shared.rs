@@ -0,0 +1,102 @@
+use demonstrate::demonstrate;
+use std::collections::VecDeque;
+
+trait Stack {
+    fn push_value(&mut self, value: u8);
+    fn pop_value(&mut self) -> Option<u8>;
+}
+
+impl Stack for Vec<u8> {
+    fn push_value(&mut self, value: u8) {
+        self.push(value)
+    }
+
+    fn pop_value(&mut self) -> Option<u8> {
+        self.pop()
+    }
+}
+
+impl Stack for VecDeque<u8> {
+    fn push_value(&mut self, value: u8) {
+        self.push_back(value)
+    }
+
+    fn pop_value(&mut self) -> Option<u8> {
+        self.pop_back()
+    }
+}
+
+demonstrate! {
+    shared "a stack" (empty: Box<dyn Stack>) {
+        before {
+            let mut stack = empty;
+        }
+
+        it "is empty" {
+            assert_eq!(stack.pop_value(), None)
+        }
+
+        it "pops the last value" {
+            stack.push_value(1);
+            stack.push_value(2);
+            assert_eq!(stack.pop_value(), Some(2))
+        }
+
+        context "with a value" {
+            before {
+                stack.push_value(value);
+            }
+
+            it "pops it" {
+                assert_eq!(stack.pop_value(), Some(value))
+            }
+        }
+    }
+
+    describe "vec" {
+        use super::*;
+
+        let value = 1;
+
+        it_behaves_like "a stack"(Box::new(Vec::new()));
+    }
+
+    describe "vec deque" {
+        use super::*;
+
+        let value = 2;
+
+        it_behaves_like "a stack"(Box::new(VecDeque::new()));
+
+        context "nested groups" {
+            shared "an accumulator" (start, step: u8) {
+                it "accumulates" for steps in [1, 3] {
+                    assert_eq!(start + step * steps, expected(steps))
+                }
+
+                shared "a doubler" {
+                    it "doubles" {
+                        assert_eq!(step * 2, step + step)
+                    }
+                }
+
+                it_behaves_like "a doubler";
+            }
+
+            context "accumulating" {
+                let expected = |steps: u8| steps * 2;
+
+                it_behaves_like "an accumulator"(0, 2);
+            }
+        }
+    }
+
+    describe "both" {
+        use super::*;
+
+        let value = 3;
+
+        it_behaves_like "a stack"(Box::new(Vec::new()));
+        it_behaves_like "a stack"(Box::new(VecDeque::new()));
+    }
+}
@@ -0,0 +1,45 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "skip" -> Result<(), String> {
+        before {
+            let value = 1;
+        }
+
+        it "runs" {
+            assert_eq!(value, 1);
+            Ok(())
+        }
+
+        xit "is skipped" {
+            Err(format!("{} should have been skipped", value))
+        }
+
+        skip "not implemented yet" it "is skipped with a reason" {
+            Err(format!("{} should have been skipped", value))
+        }
+
+        it "is pending";
+
+        it "is pending for each case" for value in [1, 2];
+
+        xcontext "skipped" {
+            it "is skipped" {
+                Err(format!("{} should have been skipped", value))
+            }
+
+            skip "flaky" it "keeps its own reason" {
+                Err(format!("{} should have been skipped", value))
+            }
+
+            it "is pending";
+        }
+
+        #[async_attributes::test]
+        skip "unreachable server" async describe "asynchronous" {
+            it "is skipped" {
+                Err(format!("{} should have been skipped", value))
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use demonstrate::{demonstrate, expect_snapshot};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static FLAKY: AtomicUsize = AtomicUsize::new(0);
+
+demonstrate! {
+    describe "snapshot" {
+        use super::*;
+
+        it "records debug output" {
+            expect_snapshot!((1, "two"))
+        }
+
+        it "records display output" {
+            expect_snapshot!(%format!("{} + {}", 1, 2))
+        }
+
+        it "numbers each snapshot" {
+            expect_snapshot!(%"first");
+            expect_snapshot!(%"second");
+        }
+
+        it "records each case" for n in [1, 2] {
+            expect_snapshot!(vec![n; n])
+        }
+
+        it "takes them from the first on each attempt" retry(1) {
+            expect_snapshot!(%"attempt");
+            assert!(FLAKY.fetch_add(1, Ordering::SeqCst) > 0, "flaky failure")
+        }
+
+        #[should_panic(expected = "- second line\n+ changed line")]
+        it "diffs mismatches" {
+            expect_snapshot!(%"first line\nchanged line\nthird line")
+        }
+    }
+
+    async describe "async snapshot" {
+        use demonstrate::expect_snapshot;
+
+        it "records output" {
+            async_std::task::yield_now().await;
+            expect_snapshot!(Some("value"))
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use demonstrate::demonstrate;
+use demonstrate::matchers::*;
+
+#[derive(Debug, PartialEq)]
+struct Money {
+    cents: u32,
+}
+
+impl Money {
+    fn new(dollars: u32, cents: u32) -> Self {
+        Money {
+            cents: dollars * 100 + cents,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.cents == 0
+    }
+}
+
+demonstrate! {
+    describe "money" {
+        use super::*;
+
+        let dollars = 1;
+
+        subject {
+            Money::new(dollars, 50)
+        }
+
+        it { is_expected.to(eq(Money { cents: 150 })) }
+
+        it { is_expected.not_to(satisfy("be zero", Money::is_zero)) }
+
+        it "has cents" => Money { cents: 150 }
+
+        it "can use the subject directly" {
+            assert_eq!(subject.cents, 150)
+        }
+
+        context "with more dollars" {
+            let dollars = 2;
+
+            it => Money { cents: 250 }
+        }
+
+        context "with nothing" {
+            subject {
+                Money::new(0, 0)
+            }
+
+            it { is_expected.to(satisfy("be zero", Money::is_zero)) }
+
+            #[should_panic(expected = "`money > with nothing > has cents` expected Money { cents: 0 } to equal Money { cents: 1 }")]
+            it "has cents" => Money { cents: 1 }
+        }
+    }
+
+    describe "generated descriptions" {
+        use demonstrate::matchers::*;
+
+        subject { vec![1, 2, 3] }
+
+        it { is_expected.to(contain(&2).and(not(be_empty()))) }
+
+        #[should_panic(expected = "`generated descriptions > is expected to contain 4` expected")]
+        it { is_expected.to(contain(&4)) }
+    }
+}
@@ -0,0 +1,99 @@
+use demonstrate::demonstrate;
+use std::process::{Command, Output};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Runs a single test of this binary by itself, along with how long it took
+fn run_alone(test: &str, args: &[&str]) -> (Output, Duration) {
+    let start = Instant::now();
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact"])
+        .args(args)
+        .output()
+        .unwrap();
+    (output, start.elapsed())
+}
+
+demonstrate! {
+    describe "timeout" timeout(1s) {
+        use super::*;
+
+        it "finishes in time" {
+            sleep(Duration::from_millis(10))
+        }
+
+        it "returns results" -> Result<(), String> {
+            "1".parse::<u8>().map_err(|error| error.to_string())?;
+            Ok(())
+        }
+
+        it "returns errors that aren't Send" -> Result<(), Box<dyn std::error::Error>> {
+            "1".parse::<u8>()?;
+            Ok(())
+        }
+
+        #[should_panic(expected = "`timeout > exceeds it` exceeded its timeout of 1s")]
+        it "exceeds it" {
+            sleep(Duration::from_secs(5))
+        }
+
+        #[should_panic(expected = "failure")]
+        it "keeps the panic" {
+            panic!("failure")
+        }
+
+        context "overridden" {
+            #[should_panic(expected = "`timeout > overridden > exceeds it` exceeded its timeout of 50ms")]
+            it "exceeds it" timeout(50ms) {
+                sleep(Duration::from_secs(5))
+            }
+
+            #[should_panic(expected = "exceeded its timeout of 50ms")]
+            it "takes a duration" timeout(Duration::from_millis(50)) {
+                sleep(Duration::from_secs(5))
+            }
+
+            it "takes fractions" timeout(0.5s) {}
+        }
+
+        it "fails near its limit" {
+            let (output, elapsed) = run_alone("timeout::overridden::exceeds_it", &[]);
+            assert!(output.status.success());
+            assert!(elapsed < Duration::from_secs(3), "took {:?}", elapsed)
+        }
+
+        #[ignore = "run by `reports errors`"]
+        it "returns an error" -> Result<(), Box<dyn std::error::Error>> {
+            "one".parse::<u8>()?;
+            Ok(())
+        }
+
+        it "reports errors" {
+            let (output, _) = run_alone("timeout::returns_an_error", &["--ignored"]);
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            assert!(!output.status.success());
+            assert!(stdout.contains("Error: ParseIntError { kind: InvalidDigit }"), "{}", stdout)
+        }
+    }
+
+    async describe "async timeout" timeout(100ms) {
+        use super::*;
+
+        it "finishes in time" {
+            async_std::task::sleep(Duration::from_millis(10)).await
+        }
+
+        #[should_panic(expected = "`async timeout > exceeds it` exceeded its timeout of 100ms")]
+        it "exceeds it" {
+            async_std::task::sleep(Duration::from_secs(5)).await
+        }
+
+        #[async_attributes::test]
+        context "on a runtime" {
+            #[should_panic(expected = "exceeded its timeout of 100ms")]
+            it "exceeds it" {
+                async_std::task::sleep(Duration::from_secs(5)).await
+            }
+        }
+    }
+}
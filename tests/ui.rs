@@ -0,0 +1,8 @@
+#[test]
+fn ui() {
+    // Focused blocks fail to compile on CI, which `focus_in_ci` checks
+    std::env::set_var("CI", "true");
+
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,15 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "outer" {
+        fixture db: u8 = 1;
+
+        context "inner" {
+            fixture tmp: u8 = 2;
+        }
+
+        it "requests a sibling's fixture" |db: &u8, tmp: &u8| {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "outline" {
+        it "is not focused" {}
+
+        context "focused" {
+            fit "is focused" {}
+        }
+    }
+}
+
+fn main() {}
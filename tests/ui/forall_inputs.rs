@@ -0,0 +1,13 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "forall" {
+        it "has cases too" for n in [1, 2] forall (m: u8) {}
+
+        it "has no inputs" forall () {}
+
+        it "has too many inputs" forall (a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8) {}
+    }
+}
+
+fn main() {}
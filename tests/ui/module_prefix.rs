@@ -0,0 +1,19 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #![module_prefix = "2"]
+
+    describe "math" {
+        it "adds" {}
+    }
+}
+
+demonstrate! {
+    #![prefix = "spec_"]
+
+    describe "math" {
+        it "adds" {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "math" {
+        it "Adds numbers" {}
+
+        it "adds-numbers" {}
+
+        it "adds" for x in [1, 2] {
+            assert!(x > 0)
+        }
+
+        it "adds with 2" {}
+
+        context "Nested Context" {}
+
+        context "nested context" {}
+    }
+}
+
+fn main() {}
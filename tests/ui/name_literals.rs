@@ -0,0 +1,13 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe 42 {
+        it "is fine" {}
+    }
+
+    describe "numbers" {
+        it 'c' {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "outer" {
+        it "has a syntax error" {
+            let x = ;
+        }
+
+        before {}
+        before {}
+
+        context "inner" {
+            it "has an unexpected token" junk {}
+
+            it "is fine" {}
+        }
+    }
+
+    describe "other" {
+        it "is fine" {}
+    }
+}
+
+fn main() {}
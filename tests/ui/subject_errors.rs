@@ -0,0 +1,13 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "duplicate subject" {
+        subject { 1 }
+
+        subject { 2 }
+
+        it => 1
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "duplicate one-liners" {
+        subject { 1 }
+
+        it => 1
+
+        it { is_expected.to(eq(1)) }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "timeouts" {
+        it "has no unit" timeout(5) {}
+
+        it "has an unknown unit" timeout(5min) {}
+    }
+}
+
+fn main() {}